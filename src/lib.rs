@@ -56,12 +56,16 @@ extern crate num;
 #[cfg(test)] extern crate rand;
 pub extern crate ocl_core as core;
 
+#[macro_use] mod macros;
 #[cfg(test)] mod tests;
 mod standard;
 
 pub use core::ffi;
 pub use standard::{Platform, Device, Context, Program, Queue, Kernel, Buffer, SubBuffer, Image,
-    Event, EventList, Sampler, SpatialDims, ProQue, MappedMem};
+    Event, EventList, UserEvent, Sampler, SpatialDims, ProQue, MappedMem, ImageLayer,
+    SamplerProperties, PixelConverter, ImageProperties, KernelPool, PooledKernel, KernelArgDesc,
+    ResourceTracker, EventTimings, Profiler, ProfileStats, Timeline, EventListIter};
+#[cfg(feature = "future")] pub use standard::{EventFuture, EventListFuture, ReadFuture};
 pub use core::error::{Error, Result};
 pub use core::util;
 
@@ -97,7 +101,7 @@ pub mod aliases {
 pub mod traits {
     //! Commonly used traits.
 
-    pub use standard::{WorkDims, MemLen, AsMemRef, AsMemMut};
+    pub use standard::{WorkDims, MemLen, AsMemRef, AsMemMut, KernelArgValue};
     pub use core::{OclPrm, OclScl, OclVec};
 }
 
@@ -106,11 +110,24 @@ pub mod builders {
 
     pub use standard::{ContextBuilder, BuildOpt, ProgramBuilder, ImageBuilder, ProQueBuilder,
         DeviceSpecifier, BufferCmd, BufferCmdKind, BufferCmdDataShape,
-        ImageCmd, ImageCmdKind, KernelCmd};
+        ImageCmd, ImageCmdKind, KernelCmd, SamplerBuilder, KernelBuilder};
     pub use core::{ImageFormat, ImageDescriptor, ContextProperties};
     // #[cfg(not(release))] pub use standard::BufferTest;
 }
 
+pub mod tune {
+    //! Kernel auto-tuning.
+
+    pub use standard::{Tuner, TuneConfig, Tuned, TUNE_CACHE_PATH_VAR as CACHE_PATH_VAR};
+}
+
+pub mod reference {
+    //! Host-side reference implementations of device behavior, useful for
+    //! verifying kernel results.
+
+    pub use standard::HostSampler;
+}
+
 pub mod flags {
     //! Bitflags for various parameter types.
 
@@ -149,7 +166,7 @@ pub mod flags {
 pub mod enums {
     //! Enumerators for settings and information requests.
 
-    pub use standard::{DeviceSpecifier, BufferCmdKind, BufferCmdDataShape};
+    pub use standard::{DeviceSpecifier, BufferCmdKind, BufferCmdDataShape, KernelArgKind};
 
     // API enums.
     pub use core::{ImageChannelOrder, ImageChannelDataType, Cbool, Polling, PlatformInfo,
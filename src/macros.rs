@@ -0,0 +1,153 @@
+//! Macros.
+
+
+/// Declares a type wrapping a `Kernel` with a typed argument list.
+///
+/// The kernel signature is declared once and the generated type provides:
+///
+/// - `new(&Program, Queue) -> Result<Self>`: Creates the kernel and declares
+///   its arguments (named after the parameters) without setting them.
+/// - `gwo`, `gws`, `lws`: Set the default work sizes (builder-style).
+/// - `set_args(args...) -> Result<&mut Self>`: Sets every argument.
+/// - A typed setter for each argument declared with `as setter_name` (such
+///   as `set_scale(f32) -> Result<&mut Self>`), which sets only that
+///   argument. (Setter names cannot be derived from argument names by
+///   `macro_rules!` and must be given.)
+/// - `enq(args...) -> Result<()>`: Sets every argument and enqueues the
+///   kernel.
+/// - `kernel() -> &Kernel` and `kernel_mut() -> &mut Kernel`: Access the
+///   underlying kernel (to use `::cmd`, to set a single argument by name,
+///   etc.).
+///
+/// Passing an argument of the wrong type to `set_args`, `enq`, or a setter is
+/// a compile error. Argument types must implement `ocl::traits::KernelArgValue`, which
+/// is implemented for references to buffers, sub-buffers, images, and
+/// samplers, and for scalar and vector primitives.
+///
+/// The type takes the name of the kernel function unless declared with
+/// `struct Name: fn kernel_name(...)`.
+///
+/// ## Examples
+///
+/// ```text
+/// ocl_kernel!(fn add(src: &Buffer<f32>, dst: &mut Buffer<f32>, scale: f32 as set_scale));
+///
+/// ocl_kernel!(pub struct Fill: fn fill(buffer: &mut Buffer<u32>, value: u32));
+///
+/// let mut kernel = add::new(&program, queue.clone())?.gws(1024);
+/// kernel.enq(&src, &mut dst, 2.0)?;
+///
+/// kernel.set_scale(3.0)?;
+/// kernel.kernel().enq()?;
+/// ```
+///
+#[macro_export]
+macro_rules! ocl_kernel {
+    (@impl $name:ident, $kname:ident, $($arg:ident: $ty:ty $(as $setter:ident)*),*) => {
+        impl $name {
+            /// Creates the kernel and declares its arguments without
+            /// setting them.
+            #[allow(dead_code)]
+            pub fn new(program: &$crate::Program, queue: $crate::Queue)
+                    -> $crate::Result<$name>
+            {
+                let kernel = try!($crate::Kernel::new(stringify!($kname), program, queue))
+                    $(.arg_unset_named(stringify!($arg)))*;
+
+                Ok($name { kernel: kernel })
+            }
+
+            /// Sets the default global work offset (builder-style).
+            #[allow(dead_code)]
+            pub fn gwo<D: Into<$crate::SpatialDims>>(self, gwo: D) -> $name {
+                $name { kernel: self.kernel.gwo(gwo) }
+            }
+
+            /// Sets the default global work size (builder-style).
+            #[allow(dead_code)]
+            pub fn gws<D: Into<$crate::SpatialDims>>(self, gws: D) -> $name {
+                $name { kernel: self.kernel.gws(gws) }
+            }
+
+            /// Sets the default local work size (builder-style).
+            #[allow(dead_code)]
+            pub fn lws<D: Into<$crate::SpatialDims>>(self, lws: D) -> $name {
+                $name { kernel: self.kernel.lws(lws) }
+            }
+
+            /// Sets every argument.
+            #[allow(dead_code)]
+            pub fn set_args(&mut self, $($arg: $ty),*) -> $crate::Result<&mut $name> {
+                let args: &[&$crate::traits::KernelArgValue] = &[$(&$arg),*];
+
+                for (arg_idx, arg) in args.iter().enumerate() {
+                    try!(arg.set_kernel_arg(&mut self.kernel, arg_idx as u32));
+                }
+
+                Ok(self)
+            }
+
+            $($(
+                /// Sets a single argument.
+                #[allow(dead_code)]
+                pub fn $setter(&mut self, $arg: $ty) -> $crate::Result<&mut $name> {
+                    let arg_idx = try!(self.kernel.named_arg_idx(stringify!($arg)).ok_or(
+                        $crate::Error::new(concat!("ocl_kernel!: Argument '", stringify!($arg),
+                            "' has not been declared."))));
+
+                    try!($crate::traits::KernelArgValue::set_kernel_arg(&$arg, &mut self.kernel,
+                        arg_idx));
+                    Ok(self)
+                }
+            )*)*
+
+            /// Sets every argument and enqueues the kernel using its
+            /// defaults.
+            #[allow(dead_code)]
+            pub fn enq(&mut self, $($arg: $ty),*) -> $crate::Result<()> {
+                try!(self.set_args($($arg),*));
+                self.kernel.enq()
+            }
+
+            /// Returns a reference to the kernel.
+            #[allow(dead_code)]
+            pub fn kernel(&self) -> &$crate::Kernel {
+                &self.kernel
+            }
+
+            /// Returns a mutable reference to the kernel.
+            #[allow(dead_code)]
+            pub fn kernel_mut(&mut self) -> &mut $crate::Kernel {
+                &mut self.kernel
+            }
+        }
+    };
+    ($(#[$attr:meta])* pub struct $name:ident:
+            fn $kname:ident($($arg:ident: $ty:ty $(as $setter:ident)*),* $(,)*)) => {
+        $(#[$attr])*
+        pub struct $name {
+            kernel: $crate::Kernel,
+        }
+
+        ocl_kernel!(@impl $name, $kname, $($arg: $ty $(as $setter)*),*);
+    };
+    ($(#[$attr:meta])* struct $name:ident:
+            fn $kname:ident($($arg:ident: $ty:ty $(as $setter:ident)*),* $(,)*)) => {
+        $(#[$attr])*
+        struct $name {
+            kernel: $crate::Kernel,
+        }
+
+        ocl_kernel!(@impl $name, $kname, $($arg: $ty $(as $setter)*),*);
+    };
+    ($(#[$attr:meta])* pub fn $kname:ident(
+            $($arg:ident: $ty:ty $(as $setter:ident)*),* $(,)*)) => {
+        ocl_kernel!($(#[$attr])* #[allow(non_camel_case_types)]
+            pub struct $kname: fn $kname($($arg: $ty $(as $setter)*),*));
+    };
+    ($(#[$attr:meta])* fn $kname:ident(
+            $($arg:ident: $ty:ty $(as $setter:ident)*),* $(,)*)) => {
+        ocl_kernel!($(#[$attr])* #[allow(non_camel_case_types)]
+            struct $kname: fn $kname($($arg: $ty $(as $setter)*),*));
+    };
+}
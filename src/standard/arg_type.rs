@@ -0,0 +1,306 @@
+//! Kernel argument type information used to validate arguments as they are
+//! set.
+
+use std::any::TypeId;
+use core::error::{Error as OclError, Result as OclResult};
+use core::{self, OclPrm, Kernel as KernelCore, Mem as MemCore, KernelArg, KernelArgInfo,
+    KernelArgInfoResult, KernelArgAddressQualifier, KernelArgAccessQualifier, MemInfo,
+    MemInfoResult, MemObjectType, OpenclVersion, MEM_READ_ONLY, MEM_WRITE_ONLY};
+use core::{ClChar2, ClChar3, ClChar4, ClChar8, ClChar16,
+    ClUchar2, ClUchar3, ClUchar4, ClUchar8, ClUchar16,
+    ClShort2, ClShort3, ClShort4, ClShort8, ClShort16,
+    ClUshort2, ClUshort3, ClUshort4, ClUshort8, ClUshort16,
+    ClInt2, ClInt3, ClInt4, ClInt8, ClInt16,
+    ClUint2, ClUint3, ClUint4, ClUint8, ClUint16,
+    ClLong1, ClLong2, ClLong3, ClLong4, ClLong8, ClLong16,
+    ClUlong1, ClUlong2, ClUlong3, ClUlong4, ClUlong8, ClUlong16,
+    ClFloat2, ClFloat3, ClFloat4, ClFloat8, ClFloat16,
+    ClDouble2, ClDouble3, ClDouble4, ClDouble8, ClDouble16};
+
+/// Built-in scalar type names which may be compared against Rust types.
+const SCALAR_TYPE_NAMES: [&'static str; 10] = ["char", "uchar", "short", "ushort", "int", "uint",
+    "long", "ulong", "float", "double"];
+
+/// Qualifiers and keywords ignored when comparing type names.
+const QUALIFIERS: [&'static str; 18] = ["const", "volatile", "restrict", "struct", "__global",
+    "global", "__constant", "constant", "__local", "local", "__private", "private", "__read_only",
+    "read_only", "__write_only", "write_only", "__read_write", "read_write"];
+
+/// Returns the OpenCL C type name corresponding to `T` or `None` if `T` is
+/// not a built-in scalar or vector type.
+fn cl_type_name<T: OclPrm>() -> Option<&'static str> {
+    let id = TypeId::of::<T>();
+
+    macro_rules! cl_type_names {
+        ( $( $ty:ty => $name:expr ),* ) => {
+            $( if id == TypeId::of::<$ty>() { return Some($name); } )*
+        };
+    }
+
+    cl_type_names!(
+        i8 => "char", u8 => "uchar", i16 => "short", u16 => "ushort", i32 => "int",
+        u32 => "uint", i64 => "long", u64 => "ulong", f32 => "float", f64 => "double",
+        ClChar2 => "char2", ClChar3 => "char3", ClChar4 => "char4", ClChar8 => "char8",
+        ClChar16 => "char16",
+        ClUchar2 => "uchar2", ClUchar3 => "uchar3", ClUchar4 => "uchar4", ClUchar8 => "uchar8",
+        ClUchar16 => "uchar16",
+        ClShort2 => "short2", ClShort3 => "short3", ClShort4 => "short4", ClShort8 => "short8",
+        ClShort16 => "short16",
+        ClUshort2 => "ushort2", ClUshort3 => "ushort3", ClUshort4 => "ushort4",
+        ClUshort8 => "ushort8", ClUshort16 => "ushort16",
+        ClInt2 => "int2", ClInt3 => "int3", ClInt4 => "int4", ClInt8 => "int8",
+        ClInt16 => "int16",
+        ClUint2 => "uint2", ClUint3 => "uint3", ClUint4 => "uint4", ClUint8 => "uint8",
+        ClUint16 => "uint16",
+        ClLong1 => "long", ClLong2 => "long2", ClLong3 => "long3", ClLong4 => "long4",
+        ClLong8 => "long8", ClLong16 => "long16",
+        ClUlong1 => "ulong", ClUlong2 => "ulong2", ClUlong3 => "ulong3", ClUlong4 => "ulong4",
+        ClUlong8 => "ulong8", ClUlong16 => "ulong16",
+        ClFloat2 => "float2", ClFloat3 => "float3", ClFloat4 => "float4", ClFloat8 => "float8",
+        ClFloat16 => "float16",
+        ClDouble2 => "double2", ClDouble3 => "double3", ClDouble4 => "double4",
+        ClDouble8 => "double8", ClDouble16 => "double16"
+    );
+
+    None
+}
+
+/// Returns the name of `T` if it is a built-in scalar or vector type.
+pub fn rust_type_name<T: OclPrm>() -> Option<&'static str> {
+    let id = TypeId::of::<T>();
+
+    macro_rules! rust_type_names {
+        ( $( $ty:ty ),* ) => {
+            $( if id == TypeId::of::<$ty>() { return Some(stringify!($ty)); } )*
+        };
+    }
+
+    rust_type_names!(i8, u8, i16, u16, i32, u32, i64, u64, isize, usize, f32, f64,
+        ClChar2, ClChar3, ClChar4, ClChar8, ClChar16,
+        ClUchar2, ClUchar3, ClUchar4, ClUchar8, ClUchar16,
+        ClShort2, ClShort3, ClShort4, ClShort8, ClShort16,
+        ClUshort2, ClUshort3, ClUshort4, ClUshort8, ClUshort16,
+        ClInt2, ClInt3, ClInt4, ClInt8, ClInt16,
+        ClUint2, ClUint3, ClUint4, ClUint8, ClUint16,
+        ClLong1, ClLong2, ClLong3, ClLong4, ClLong8, ClLong16,
+        ClUlong1, ClUlong2, ClUlong3, ClUlong4, ClUlong8, ClUlong16,
+        ClFloat2, ClFloat3, ClFloat4, ClFloat8, ClFloat16,
+        ClDouble2, ClDouble3, ClDouble4, ClDouble8, ClDouble16);
+
+    None
+}
+
+/// Splits a type name such as `"float4"` into its scalar name and vector
+/// width (`("float", 4)`). Scalars have a width of one.
+fn split_vector_name(name: &str) -> (&str, u32) {
+    let scalar = name.trim_right_matches(|c: char| c.is_digit(10));
+    let width = name[scalar.len()..].parse().unwrap_or(1);
+    (scalar, width)
+}
+
+/// Returns true if `name` is a built-in scalar or vector type name.
+fn is_builtin(name: &str) -> bool {
+    let (scalar, width) = split_vector_name(name);
+    SCALAR_TYPE_NAMES.contains(&scalar) && [1, 2, 3, 4, 8, 16].contains(&width)
+}
+
+/// Returns true if the built-in type names, `rust_name` and `cl_name`, are
+/// interchangeable. Three component vectors have the same size and
+/// alignment as four component vectors and are treated as equivalent.
+fn names_match(rust_name: &str, cl_name: &str) -> bool {
+    if rust_name == cl_name { return true; }
+    let (rust_scalar, rust_width) = split_vector_name(rust_name);
+    let (cl_scalar, cl_width) = split_vector_name(cl_name);
+    rust_scalar == cl_scalar && rust_width >= 3 && rust_width <= 4 &&
+        cl_width >= 3 && cl_width <= 4
+}
+
+
+/// The type of a kernel argument (parameter) as declared within the kernel.
+///
+/// Only available when the program was built with `-cl-kernel-arg-info`.
+///
+#[derive(Clone, Debug)]
+pub struct ArgType {
+    name: String,
+    type_name: String,
+    base_type_name: String,
+    is_ptr: bool,
+    address: KernelArgAddressQualifier,
+    access: KernelArgAccessQualifier,
+}
+
+impl ArgType {
+    /// Queries the type of the argument at `arg_idx`.
+    pub fn from_kernel(kernel: &KernelCore, arg_idx: u32, device_version: OpenclVersion)
+            -> OclResult<ArgType>
+    {
+        let name = match core::get_kernel_arg_info(kernel, arg_idx, KernelArgInfo::Name,
+                Some(&[device_version])) {
+            KernelArgInfoResult::Name(n) => n,
+            res => return arg_info_err(res),
+        };
+        let type_name = match core::get_kernel_arg_info(kernel, arg_idx, KernelArgInfo::TypeName,
+                Some(&[device_version])) {
+            KernelArgInfoResult::TypeName(n) => n,
+            res => return arg_info_err(res),
+        };
+        let address = match core::get_kernel_arg_info(kernel, arg_idx,
+                KernelArgInfo::AddressQualifier, Some(&[device_version])) {
+            KernelArgInfoResult::AddressQualifier(a) => a,
+            res => return arg_info_err(res),
+        };
+        let access = match core::get_kernel_arg_info(kernel, arg_idx,
+                KernelArgInfo::AccessQualifier, Some(&[device_version])) {
+            KernelArgInfoResult::AccessQualifier(a) => a,
+            res => return arg_info_err(res),
+        };
+
+        let type_name = type_name.trim_right_matches('\0').trim().to_owned();
+        let is_ptr = type_name.ends_with('*');
+
+        // Remove qualifiers, normalize spelling, and dereference pointers:
+        let base_type_name = type_name.trim_right_matches(|c: char| c == '*' || c.is_whitespace())
+            .split_whitespace()
+            .filter(|w| !QUALIFIERS.contains(w))
+            .collect::<Vec<_>>()
+            .join(" ")
+            .replace("unsigned ", "u");
+
+        Ok(ArgType {
+            name: name.trim_right_matches('\0').to_owned(),
+            type_name: type_name,
+            base_type_name: base_type_name,
+            is_ptr: is_ptr,
+            address: address,
+            access: access,
+        })
+    }
+
+    /// Returns the parameter name as declared in the kernel.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the type name as declared in the kernel (e.g. `"float*"`).
+    pub fn type_name(&self) -> &str {
+        &self.type_name
+    }
+
+    /// Returns true if this is an image type (`image2d_t`, etc.).
+    pub fn is_image(&self) -> bool {
+        self.base_type_name.starts_with("image") && self.base_type_name.ends_with("_t")
+    }
+
+    /// Returns true if this is a sampler.
+    pub fn is_sampler(&self) -> bool {
+        self.base_type_name == "sampler_t"
+    }
+
+    /// Returns true if this is a pointer to global or constant memory.
+    fn is_mem_ptr(&self) -> bool {
+        match self.address {
+            KernelArgAddressQualifier::Global | KernelArgAddressQualifier::Constant => self.is_ptr,
+            _ => false,
+        }
+    }
+
+    /// Verifies that the (pointed-to) type matches `T` if both are built-in
+    /// types. User defined types and typedefs are not checked.
+    fn check_type_name<T: OclPrm>(&self) -> OclResult<()> {
+        if let Some(rust_name) = cl_type_name::<T>() {
+            if is_builtin(&self.base_type_name) && !names_match(rust_name, &self.base_type_name) {
+                return OclError::err(format!("The argument type ('{}') does not match the \
+                    declared type ('{}').", rust_name, self.type_name));
+            }
+        }
+        Ok(())
+    }
+
+    /// Verifies that `arg` is compatible with this argument type.
+    pub fn check<T: OclPrm>(&self, arg: &KernelArg<T>) -> OclResult<()> {
+        match *arg {
+            KernelArg::Mem(mem) => {
+                match try!(mem_object_type(mem)) {
+                    MemObjectType::Buffer => {
+                        if !self.is_mem_ptr() {
+                            return self.err_kind("a buffer");
+                        }
+                        self.check_type_name::<T>()
+                    },
+                    _ => {
+                        if !self.is_image() {
+                            return self.err_kind("an image");
+                        }
+                        self.check_image_access(mem)
+                    },
+                }
+            },
+            KernelArg::MemNull => {
+                if !self.is_mem_ptr() {
+                    return self.err_kind("a null buffer");
+                }
+                self.check_type_name::<T>()
+            },
+            KernelArg::Sampler(_) | KernelArg::SamplerNull => {
+                if self.is_sampler() { Ok(()) } else { self.err_kind("a sampler") }
+            },
+            KernelArg::Scalar(_) | KernelArg::Vector(_) => {
+                if self.is_ptr || self.is_image() || self.is_sampler() {
+                    return self.err_kind("a scalar or vector value");
+                }
+                self.check_type_name::<T>()
+            },
+            KernelArg::Local(_) => {
+                match self.address {
+                    KernelArgAddressQualifier::Local if self.is_ptr => self.check_type_name::<T>(),
+                    _ => self.err_kind("a local memory allocation"),
+                }
+            },
+            _ => Ok(()),
+        }
+    }
+
+    /// Verifies that an image created as read-only is not bound to a
+    /// `write_only` argument and vice versa.
+    fn check_image_access(&self, mem: &MemCore) -> OclResult<()> {
+        let flags = match core::get_mem_object_info(mem, MemInfo::Flags) {
+            MemInfoResult::Flags(f) => f,
+            MemInfoResult::Error(err) => return Err(*err),
+            _ => return OclError::err("Unexpected 'MemInfoResult' variant."),
+        };
+
+        match self.access {
+            KernelArgAccessQualifier::WriteOnly if flags.contains(MEM_READ_ONLY) => {
+                OclError::err(format!("A read-only image ('MEM_READ_ONLY') cannot be bound to \
+                    a 'write_only' argument ('{}').", self.type_name))
+            },
+            KernelArgAccessQualifier::ReadOnly if flags.contains(MEM_WRITE_ONLY) => {
+                OclError::err(format!("A write-only image ('MEM_WRITE_ONLY') cannot be bound to \
+                    a 'read_only' argument ('{}').", self.type_name))
+            },
+            _ => Ok(()),
+        }
+    }
+
+    fn err_kind(&self, kind: &str) -> OclResult<()> {
+        OclError::err(format!("Cannot set {} to an argument declared as '{}' ({:?}).", kind,
+            self.type_name, self.address))
+    }
+}
+
+/// Returns the type of a memory object (buffer, image, etc.).
+pub fn mem_object_type(mem: &MemCore) -> OclResult<MemObjectType> {
+    match core::get_mem_object_info(mem, MemInfo::Type) {
+        MemInfoResult::Type(t) => Ok(t),
+        MemInfoResult::Error(err) => Err(*err),
+        _ => OclError::err("Unexpected 'MemInfoResult' variant."),
+    }
+}
+
+fn arg_info_err<T>(res: KernelArgInfoResult) -> OclResult<T> {
+    match res {
+        KernelArgInfoResult::Error(err) => Err(*err),
+        _ => OclError::err("Unexpected 'KernelArgInfoResult' variant."),
+    }
+}
@@ -11,9 +11,11 @@ use ffi::cl_GLuint;
 
 use core::{self, OclPrm, Mem as MemCore, CommandQueue, MemFlags, MemInfo, MemInfoResult,
     ClEventPtrNew, ClWaitList, BufferRegion, MappedMem as MappedMemCore, Event as EventCore,
-    EventList as EventListCore, MapFlags};
+    EventList as EventListCore, MapFlags, KernelArg};
 use core::error::{Error as OclError, Result as OclResult};
-use standard::{Queue, MemLen, SpatialDims, AsMemRef};
+use standard::{Queue, MemLen, SpatialDims, AsMemRef, Kernel, KernelArgValue, Resource};
+use standard::tracker::copy_host_data;
+#[cfg(feature = "future")] use standard::{Event, ReadFuture};
 
 
 fn check_len(mem_len: usize, data_len: usize, offset: usize) -> OclResult<()> {
@@ -28,6 +30,21 @@ fn check_len(mem_len: usize, data_len: usize, offset: usize) -> OclResult<()> {
 }
 
 
+/// Enqueues a non-blocking read of all `len` elements of `obj_core`, returning
+/// a future which resolves to them. Used by `Buffer` and `SubBuffer`.
+#[cfg(feature = "future")]
+fn read_async<T: OclPrm>(queue: &Queue, obj_core: &MemCore, len: usize) -> ReadFuture<T> {
+    let mut data = vec![Default::default(); len];
+    let mut event = Event::empty();
+
+    unsafe {
+        let res = BufferCmd::new(queue, obj_core, len).read_async(&mut data)
+            .enew(&mut event).enq();
+        ReadFuture::new(data, event, res)
+    }
+}
+
+
 /// Information about what to do when `MappedMem` goes out of scope.
 enum DelayedUnmap {
     Some {
@@ -523,103 +540,121 @@ impl<'b, T: 'b + OclPrm> BufferCmd<'b, T> {
     /// For map operations use `::enq_map` instead.
     ///
     pub fn enq(self) -> OclResult<()> {
-        match self.kind {
-            BufferCmdKind::Read { data } => {
-                match self.shape {
-                    BufferCmdDataShape::Lin { offset } => {
-                        try!(check_len(self.mem_len, data.len(), offset));
+        let BufferCmd { queue, obj_core, block, kind, shape, ewait, enew, mem_len, .. } = self;
 
-                        unsafe { core::enqueue_read_buffer(self.queue, self.obj_core, self.block,
-                            offset, data, self.ewait, self.enew) }
-                    },
-                    BufferCmdDataShape::Rect { src_origin, dst_origin, region, src_row_pitch, src_slc_pitch,
-                            dst_row_pitch, dst_slc_pitch } =>
-                    {
-                        // Verify dims given.
-                        // try!(Ok(()));
-
-                        unsafe { core::enqueue_read_buffer_rect(self.queue, self.obj_core,
-                            self.block, src_origin, dst_origin, region, src_row_pitch,
-                            src_slc_pitch, dst_row_pitch, dst_slc_pitch, data,
-                            self.ewait, self.enew) }
-                    }
-                }
-            },
-            BufferCmdKind::Write { data } => {
-                match self.shape {
-                    BufferCmdDataShape::Lin { offset } => {
-                        try!(check_len(self.mem_len, data.len(), offset));
-                        core::enqueue_write_buffer(self.queue, self.obj_core, self.block,
-                            offset, data, self.ewait, self.enew)
-                    },
-                    BufferCmdDataShape::Rect { src_origin, dst_origin, region, src_row_pitch, src_slc_pitch,
-                            dst_row_pitch, dst_slc_pitch } =>
-                    {
-                        // Verify dims given.
-                        // try!(Ok(()));
-
-                        core::enqueue_write_buffer_rect(self.queue, self.obj_core,
-                            self.block, src_origin, dst_origin, region, src_row_pitch,
-                            src_slc_pitch, dst_row_pitch, dst_slc_pitch, data,
-                            self.ewait, self.enew)
-                    }
-                }
+        // Retain the buffers until the command completes:
+        let mut resources = vec![Resource::Mem(obj_core.clone())];
+        if let BufferCmdKind::Copy { dst_buffer, .. } = kind {
+            resources.push(Resource::Mem(dst_buffer.clone()));
+        }
+
+        // Non-blocking writes read from a retained copy of the data:
+        let kind = match kind {
+            BufferCmdKind::Write { data } if !block => {
+                BufferCmdKind::Write { data: unsafe { copy_host_data(data, &mut resources) } }
             },
-            BufferCmdKind::Copy { dst_buffer, dst_offset, len } => {
-                match self.shape {
-                    BufferCmdDataShape::Lin { offset } => {
-                        let len = len.unwrap_or(self.mem_len);
-                        try!(check_len(self.mem_len, len, offset));
-                        let dst_offset = dst_offset.unwrap_or(0);
+            kind => kind,
+        };
 
-                        core::enqueue_copy_buffer::<T>(self.queue,
-                            self.obj_core, dst_buffer, offset, dst_offset, len,
-                            self.ewait, self.enew)
-                    },
-                    BufferCmdDataShape::Rect { src_origin, dst_origin, region, src_row_pitch, src_slc_pitch,
-                            dst_row_pitch, dst_slc_pitch } =>
-                    {
-                        // Verify dims given.
-                        // try!(Ok(()));
-
-                        if dst_offset.is_some() || len.is_some() { return OclError::err(
-                            "ocl::BufferCmd::enq(): For 'rect' shaped copies, destination \
-                            offset and length must be 'None'. Ex.: \
-                            'cmd().copy(&{{buf_name}}, None, None)..'.");
+        queue.tracker().enqueue(resources, enew, |enew| {
+            match kind {
+                BufferCmdKind::Read { data } => {
+                    match shape {
+                        BufferCmdDataShape::Lin { offset } => {
+                            try!(check_len(mem_len, data.len(), offset));
+
+                            unsafe { core::enqueue_read_buffer(queue, obj_core, block,
+                                offset, data, ewait, enew) }
+                        },
+                        BufferCmdDataShape::Rect { src_origin, dst_origin, region, src_row_pitch, src_slc_pitch,
+                                dst_row_pitch, dst_slc_pitch } =>
+                        {
+                            // Verify dims given.
+                            // try!(Ok(()));
+
+                            unsafe { core::enqueue_read_buffer_rect(queue, obj_core,
+                                block, src_origin, dst_origin, region, src_row_pitch,
+                                src_slc_pitch, dst_row_pitch, dst_slc_pitch, data,
+                                ewait, enew) }
                         }
-                        core::enqueue_copy_buffer_rect::<T>(self.queue, self.obj_core, dst_buffer,
-                            src_origin, dst_origin, region, src_row_pitch, src_slc_pitch,
-                            dst_row_pitch, dst_slc_pitch, self.ewait, self.enew)
-                    },
-                }
-            },
-            BufferCmdKind::Fill { pattern, len } => {
-                match self.shape {
-                    BufferCmdDataShape::Lin { offset } => {
-                        let len = match len {
-                            Some(l) => l,
-                            None => self.mem_len,
-                        };
-                        try!(check_len(self.mem_len, len, offset));
-                        core::enqueue_fill_buffer(self.queue, self.obj_core, pattern,
-                            offset, len, self.ewait, self.enew, Some(&self.queue.device_version()))
-                    },
-                    BufferCmdDataShape::Rect { .. } => OclError::err("ocl::BufferCmd::enq(): \
-                        Rectangular fill is not a valid operation. Please use the default shape, linear.")
-                }
-            },
-            BufferCmdKind::GLAcquire => {
-                core::enqueue_acquire_gl_buffer(self.queue, self.obj_core, self.ewait, self.enew)
-            },
-            BufferCmdKind::GLRelease => {
-                core::enqueue_release_gl_buffer(self.queue, self.obj_core, self.ewait, self.enew)
-            },
-            BufferCmdKind::Unspecified => OclError::err("ocl::BufferCmd::enq(): No operation \
-                specified. Use '.read(...)', 'write(...)', etc. before calling '.enq()'."),
-            BufferCmdKind::Map { .. } => OclError::err("ocl::BufferCmd::enq(): \
-                For map operations use '::enq_map()' instead."),
-            _ => unimplemented!(),
-        }
+                    }
+                },
+                BufferCmdKind::Write { data } => {
+                    match shape {
+                        BufferCmdDataShape::Lin { offset } => {
+                            try!(check_len(mem_len, data.len(), offset));
+                            core::enqueue_write_buffer(queue, obj_core, block,
+                                offset, data, ewait, enew)
+                        },
+                        BufferCmdDataShape::Rect { src_origin, dst_origin, region, src_row_pitch, src_slc_pitch,
+                                dst_row_pitch, dst_slc_pitch } =>
+                        {
+                            // Verify dims given.
+                            // try!(Ok(()));
+
+                            core::enqueue_write_buffer_rect(queue, obj_core,
+                                block, src_origin, dst_origin, region, src_row_pitch,
+                                src_slc_pitch, dst_row_pitch, dst_slc_pitch, data,
+                                ewait, enew)
+                        }
+                    }
+                },
+                BufferCmdKind::Copy { dst_buffer, dst_offset, len } => {
+                    match shape {
+                        BufferCmdDataShape::Lin { offset } => {
+                            let len = len.unwrap_or(mem_len);
+                            try!(check_len(mem_len, len, offset));
+                            let dst_offset = dst_offset.unwrap_or(0);
+
+                            core::enqueue_copy_buffer::<T>(queue,
+                                obj_core, dst_buffer, offset, dst_offset, len,
+                                ewait, enew)
+                        },
+                        BufferCmdDataShape::Rect { src_origin, dst_origin, region, src_row_pitch, src_slc_pitch,
+                                dst_row_pitch, dst_slc_pitch } =>
+                        {
+                            // Verify dims given.
+                            // try!(Ok(()));
+
+                            if dst_offset.is_some() || len.is_some() { return OclError::err(
+                                "ocl::BufferCmd::enq(): For 'rect' shaped copies, destination \
+                                offset and length must be 'None'. Ex.: \
+                                'cmd().copy(&{{buf_name}}, None, None)..'.");
+                            }
+                            core::enqueue_copy_buffer_rect::<T>(queue, obj_core, dst_buffer,
+                                src_origin, dst_origin, region, src_row_pitch, src_slc_pitch,
+                                dst_row_pitch, dst_slc_pitch, ewait, enew)
+                        },
+                    }
+                },
+                BufferCmdKind::Fill { pattern, len } => {
+                    match shape {
+                        BufferCmdDataShape::Lin { offset } => {
+                            let len = match len {
+                                Some(l) => l,
+                                None => mem_len,
+                            };
+                            try!(check_len(mem_len, len, offset));
+                            core::enqueue_fill_buffer(queue, obj_core, pattern,
+                                offset, len, ewait, enew, Some(&queue.device_version()))
+                        },
+                        BufferCmdDataShape::Rect { .. } => OclError::err("ocl::BufferCmd::enq(): \
+                            Rectangular fill is not a valid operation. Please use the default shape, linear.")
+                    }
+                },
+                BufferCmdKind::GLAcquire => {
+                    core::enqueue_acquire_gl_buffer(queue, obj_core, ewait, enew)
+                },
+                BufferCmdKind::GLRelease => {
+                    core::enqueue_release_gl_buffer(queue, obj_core, ewait, enew)
+                },
+                BufferCmdKind::Unspecified => OclError::err("ocl::BufferCmd::enq(): No operation \
+                    specified. Use '.read(...)', 'write(...)', etc. before calling '.enq()'."),
+                BufferCmdKind::Map { .. } => OclError::err("ocl::BufferCmd::enq(): \
+                    For map operations use '::enq_map()' instead."),
+                _ => unimplemented!(),
+            }
+        })
     }
 
     /// Enqueues a map command.
@@ -791,6 +826,15 @@ impl<T: OclPrm> Buffer<T> {
         self.cmd().write(data)
     }
 
+    /// Enqueues a non-blocking read of the entire buffer, returning a future
+    /// which resolves to its contents.
+    ///
+    /// Errors enqueuing the command are returned when the future is polled.
+    #[cfg(feature = "future")]
+    pub fn read_async(&self) -> ReadFuture<T> {
+        read_async(&self.queue, &self.obj_core, self.len)
+    }
+
     /// Returns the length of the buffer.
     #[inline]
     pub fn len(&self) -> usize {
@@ -941,6 +985,24 @@ impl<'a, T: OclPrm> AsMemRef<T> for &'a mut Buffer<T> {
     }
 }
 
+impl<'a, T: OclPrm> From<&'a Buffer<T>> for KernelArg<'a, T> {
+    fn from(buffer: &'a Buffer<T>) -> KernelArg<'a, T> {
+        KernelArg::Mem(&buffer.obj_core)
+    }
+}
+
+impl<'a, T: OclPrm> KernelArgValue for &'a Buffer<T> {
+    fn set_kernel_arg(&self, kernel: &mut Kernel, arg_idx: u32) -> OclResult<()> {
+        kernel.set_arg::<T>(arg_idx, KernelArg::Mem(&self.obj_core))
+    }
+}
+
+impl<'a, T: OclPrm> KernelArgValue for &'a mut Buffer<T> {
+    fn set_kernel_arg(&self, kernel: &mut Kernel, arg_idx: u32) -> OclResult<()> {
+        kernel.set_arg::<T>(arg_idx, KernelArg::Mem(&self.obj_core))
+    }
+}
+
 // impl<T: OclPrm> AsMemRef<T> for Buffer<T> {
 //     fn as_mem_ref(&mut self) -> &mut MemCore {
 //         &self.obj_core
@@ -1064,6 +1126,15 @@ impl<T: OclPrm> SubBuffer<T> {
         self.cmd().write(data)
     }
 
+    /// Enqueues a non-blocking read of the entire sub-buffer, returning a future
+    /// which resolves to its contents.
+    ///
+    /// Errors enqueuing the command are returned when the future is polled.
+    #[cfg(feature = "future")]
+    pub fn read_async(&self) -> ReadFuture<T> {
+        read_async(&self.queue, &self.obj_core, self.len)
+    }
+
     /// Returns the origin of the sub-buffer within the buffer.
     #[inline]
     pub fn origin(&self) -> &SpatialDims {
@@ -1189,6 +1260,24 @@ impl<'a, T: OclPrm> AsMemRef<T> for &'a mut SubBuffer<T> {
     }
 }
 
+impl<'a, T: OclPrm> From<&'a SubBuffer<T>> for KernelArg<'a, T> {
+    fn from(sub_buffer: &'a SubBuffer<T>) -> KernelArg<'a, T> {
+        KernelArg::Mem(&sub_buffer.obj_core)
+    }
+}
+
+impl<'a, T: OclPrm> KernelArgValue for &'a SubBuffer<T> {
+    fn set_kernel_arg(&self, kernel: &mut Kernel, arg_idx: u32) -> OclResult<()> {
+        kernel.set_arg::<T>(arg_idx, KernelArg::Mem(&self.obj_core))
+    }
+}
+
+impl<'a, T: OclPrm> KernelArgValue for &'a mut SubBuffer<T> {
+    fn set_kernel_arg(&self, kernel: &mut Kernel, arg_idx: u32) -> OclResult<()> {
+        kernel.set_arg::<T>(arg_idx, KernelArg::Mem(&self.obj_core))
+    }
+}
+
 impl<T: OclPrm> std::fmt::Display for SubBuffer<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         self.fmt_mem_info(f)
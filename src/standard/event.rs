@@ -1,13 +1,22 @@
 //! An `OpenCL` event.
 
 use std;
-use std::ops::{Deref, DerefMut};
+use std::ops::{Deref, DerefMut, Index};
 use std::convert::Into;
+use std::iter::FromIterator;
+use std::time::{Duration, Instant};
+use std::sync::{Arc, Mutex, Condvar};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::panic::{self, AssertUnwindSafe};
 use libc::c_void;
 use ffi;
 use core::error::{Error as OclError, Result as OclResult};
 use core::{self, Event as EventCore, EventInfo, EventInfoResult, ProfilingInfo, ProfilingInfoResult,
-    ClEventPtrNew, ClWaitList, EventList as EventListCore, CommandExecutionStatus, EventCallbackFn};
+    ClEventPtrNew, ClWaitList, EventList as EventListCore, CommandExecutionStatus, EventCallbackFn,
+    CommandQueueInfo, CommandQueueInfoResult, QUEUE_PROFILING_ENABLE};
+use core::Context as ContextCore;
+use standard::Context;
+#[cfg(feature = "future")] use standard::{EventFuture, EventListFuture};
 
 /// An event representing a command or user created event.
 ///
@@ -63,6 +72,86 @@ impl Event {
         core::wait_for_event(self.0.as_ref().unwrap())
     }
 
+    /// Calls `callback` once the command associated with this event
+    /// completes, from a thread managed by the `OpenCL` runtime.
+    ///
+    /// `callback` receives `Ok(CommandExecutionStatus::Complete)` or, if the
+    /// command terminated abnormally, an error containing the error code.
+    /// Callbacks should return quickly and must not call blocking functions
+    /// such as `::wait` or `Queue::finish`.
+    pub fn on_complete<F>(&self, callback: F) -> OclResult<()>
+            where F: FnOnce(OclResult<CommandExecutionStatus>) + Send + 'static
+    {
+        self.on_status(CommandExecutionStatus::Complete, callback)
+    }
+
+    /// Returns a future which resolves once the command associated with this
+    /// event has completed.
+    #[cfg(feature = "future")]
+    pub fn future(&self) -> EventFuture {
+        EventFuture::new(self.clone())
+    }
+
+    /// Calls `callback` once the command associated with this event has been
+    /// submitted to the device.
+    ///
+    /// See `::on_complete`.
+    pub fn on_submitted<F>(&self, callback: F) -> OclResult<()>
+            where F: FnOnce(OclResult<CommandExecutionStatus>) + Send + 'static
+    {
+        self.on_status(CommandExecutionStatus::Submitted, callback)
+    }
+
+    /// Calls `callback` once the command associated with this event has
+    /// started running.
+    ///
+    /// See `::on_complete`.
+    pub fn on_running<F>(&self, callback: F) -> OclResult<()>
+            where F: FnOnce(OclResult<CommandExecutionStatus>) + Send + 'static
+    {
+        self.on_status(CommandExecutionStatus::Running, callback)
+    }
+
+    /// Calls `callback` once the command associated with this event reaches
+    /// `status` (or terminates abnormally).
+    ///
+    /// The callback is boxed and freed after it runs. Panics within it are
+    /// caught (unwinding into the `OpenCL` runtime is undefined behavior).
+    /// See `::on_complete`.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if this event is empty, if `status` is
+    /// `CommandExecutionStatus::Queued` (callbacks cannot be registered for
+    /// it), or if the callback cannot be set.
+    pub fn on_status<F>(&self, status: CommandExecutionStatus, callback: F) -> OclResult<()>
+            where F: FnOnce(OclResult<CommandExecutionStatus>) + Send + 'static
+    {
+        let core = match self.0 {
+            Some(ref core) => core,
+            None => return Err(self.err_empty()),
+        };
+
+        if let CommandExecutionStatus::Queued = status {
+            return OclError::err("ocl::Event::on_status(): Callbacks cannot be set for the \
+                'Queued' status.");
+        }
+
+        let mut callback = Some(callback);
+        let callback: StatusCallback = Box::new(move |status| {
+            if let Some(callback) = callback.take() { callback(status) }
+        });
+        let callback_ptr = Box::into_raw(Box::new(callback));
+
+        if let Err(err) = core::set_event_callback(core, status, Some(run_status_callback),
+                callback_ptr as *mut c_void) {
+            unsafe { drop(Box::from_raw(callback_ptr)); }
+            return Err(err);
+        }
+
+        Ok(())
+    }
+
     /// Returns true if this event is 'empty' and has not yet been associated
     /// with a command.
     #[inline]
@@ -100,6 +189,60 @@ impl Event {
         }
     }
 
+    /// Returns the queued, submit, start, and end times of the command
+    /// associated with this event.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if this event is empty or is a user event, if its
+    /// queue was not created with the `QUEUE_PROFILING_ENABLE` flag, or if
+    /// the command has not yet completed.
+    pub fn timings(&self) -> OclResult<EventTimings> {
+        let queue = match self.info(EventInfo::CommandQueue) {
+            EventInfoResult::CommandQueue(queue) => queue,
+            EventInfoResult::Error(err) => return Err(*err),
+            _ => return OclError::err("ocl::Event::timings(): Unexpected 'EventInfoResult' \
+                variant."),
+        };
+
+        let profiling_enabled = match core::get_command_queue_info(&queue,
+                CommandQueueInfo::Properties) {
+            CommandQueueInfoResult::Properties(props) => props.contains(QUEUE_PROFILING_ENABLE),
+            // User events have no queue:
+            CommandQueueInfoResult::Error(_) => false,
+            _ => return OclError::err("ocl::Event::timings(): Unexpected \
+                'CommandQueueInfoResult' variant."),
+        };
+
+        if !profiling_enabled {
+            return OclError::err("ocl::Event::timings(): Profiling is not enabled for the queue \
+                associated with this event. Create the queue with the 'QUEUE_PROFILING_ENABLE' \
+                flag.");
+        }
+
+        if !try!(self.is_complete()) {
+            return OclError::err("ocl::Event::timings(): The command associated with this event \
+                has not completed.");
+        }
+
+        Ok(EventTimings {
+            queued: try!(self.profiling_time(ProfilingInfo::Queued)),
+            submit: try!(self.profiling_time(ProfilingInfo::Submit)),
+            start: try!(self.profiling_time(ProfilingInfo::Start)),
+            end: try!(self.profiling_time(ProfilingInfo::End)),
+        })
+    }
+
+    fn profiling_time(&self, info_kind: ProfilingInfo) -> OclResult<Duration> {
+        match self.profiling_info(info_kind) {
+            ProfilingInfoResult::Queued(ns) | ProfilingInfoResult::Submit(ns) |
+                ProfilingInfoResult::Start(ns) | ProfilingInfoResult::End(ns) => {
+                Ok(Duration::new(ns / 1_000_000_000, (ns % 1_000_000_000) as u32))
+            },
+            ProfilingInfoResult::Error(err) => Err(*err),
+        }
+    }
+
     /// Returns a reference to the core pointer wrapper, usable by functions in
     /// the `core` module.
     #[inline]
@@ -218,6 +361,239 @@ unsafe impl ClWaitList for Event {
 }
 
 
+/// The profiling times of a command, measured by the device clock (in time
+/// since an arbitrary, device-specific, epoch).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EventTimings {
+    /// When the command was enqueued.
+    pub queued: Duration,
+    /// When the command was submitted to the device.
+    pub submit: Duration,
+    /// When the command started executing.
+    pub start: Duration,
+    /// When the command finished executing.
+    pub end: Duration,
+}
+
+impl EventTimings {
+    /// Returns the time spent executing (from start to end).
+    pub fn duration(&self) -> Duration {
+        self.end.checked_sub(self.start).unwrap_or(Duration::new(0, 0))
+    }
+
+    /// Returns the time from when the command was enqueued until it started
+    /// executing.
+    pub fn latency(&self) -> Duration {
+        self.start.checked_sub(self.queued).unwrap_or(Duration::new(0, 0))
+    }
+
+    /// Returns the time from when the command was enqueued until it finished
+    /// executing.
+    pub fn total(&self) -> Duration {
+        self.end.checked_sub(self.queued).unwrap_or(Duration::new(0, 0))
+    }
+}
+
+
+/// A boxed status callback (called at most once).
+type StatusCallback = Box<FnMut(OclResult<CommandExecutionStatus>) + Send>;
+
+extern "C" fn run_status_callback(_: ffi::cl_event, status: ffi::cl_int, callback: *mut c_void) {
+    let mut callback = unsafe { Box::from_raw(callback as *mut StatusCallback) };
+    let status = status_from_code(status);
+
+    // Panics have already printed their messages and must not unwind into
+    // the runtime:
+    let _ = panic::catch_unwind(AssertUnwindSafe(|| callback(status)));
+}
+
+/// Returns the raw execution status of `event`: one of `CL_QUEUED`,
+/// `CL_SUBMITTED`, `CL_RUNNING`, `CL_COMPLETE`, or a negative error code if
+/// the command terminated abnormally.
+pub fn execution_status_code(event: &Event) -> OclResult<ffi::cl_int> {
+    if event.is_empty() { return Err(event.err_empty()); }
+
+    let mut status: ffi::cl_int = 0;
+    let errcode = unsafe { ffi::clGetEventInfo(*event.as_ptr_ptr(),
+        ffi::CL_EVENT_COMMAND_EXECUTION_STATUS, std::mem::size_of::<ffi::cl_int>(),
+        &mut status as *mut ffi::cl_int as *mut c_void, 0 as *mut usize) };
+
+    if errcode != ffi::CL_SUCCESS {
+        return OclError::err(format!("ocl::Event: 'clGetEventInfo' failed with error code: {}.",
+            errcode));
+    }
+
+    Ok(status)
+}
+
+/// Converts a raw execution status into a `CommandExecutionStatus` or, if
+/// negative, an error.
+pub fn status_from_code(code: ffi::cl_int) -> OclResult<CommandExecutionStatus> {
+    match code {
+        ffi::CL_COMPLETE => Ok(CommandExecutionStatus::Complete),
+        ffi::CL_RUNNING => Ok(CommandExecutionStatus::Running),
+        ffi::CL_SUBMITTED => Ok(CommandExecutionStatus::Submitted),
+        ffi::CL_QUEUED => Ok(CommandExecutionStatus::Queued),
+        code => OclError::err(format!("ocl::Event: The command terminated abnormally with \
+            error code: {}.", code)),
+    }
+}
+
+/// Creates a new user event associated with `context`. The event is only
+/// filled once creation has succeeded.
+pub fn create_user_event(context: &ContextCore) -> OclResult<Event> {
+    let mut errcode: ffi::cl_int = 0;
+    let event_ptr = unsafe { ffi::clCreateUserEvent(context.as_ptr(), &mut errcode) };
+
+    if errcode != ffi::CL_SUCCESS {
+        return OclError::err(format!("ocl::UserEvent::new(): 'clCreateUserEvent' failed \
+            with error code: {}.", errcode));
+    }
+
+    let mut event = Event::empty();
+    unsafe { *try!(event.ptr_mut_ptr_new()) = event_ptr; }
+    Ok(event)
+}
+
+
+/// The error status set when a `UserEvent` is dropped without having been
+/// set.
+const DROPPED_STATUS: ffi::cl_int = -1;
+
+/// An event whose status is set from the host, used to hold back enqueued
+/// commands (which list it in their wait list) until some host-side
+/// condition is met.
+///
+/// The status can be set only once, using `::set_complete` or `::set_error`,
+/// from any thread. A user event dropped without its status having been set
+/// is set automatically: to an error status (-1) by default,
+/// causing commands waiting on it to fail, or to complete if
+/// `::set_complete_on_drop` has been called. Commands waiting on it will
+/// never hang because it was forgotten.
+///
+/// Dereferences to `Event`. Clones of that `Event` (for use in `EventList`s
+/// for example) do not keep this user event from being set when dropped.
+///
+#[derive(Debug)]
+pub struct UserEvent {
+    event: Event,
+    is_set: AtomicBool,
+    complete_on_drop: bool,
+}
+
+impl UserEvent {
+    /// Creates a new user event associated with `context`. It can be waited
+    /// on by commands enqueued on any queue using the same context.
+    pub fn new(context: &Context) -> OclResult<UserEvent> {
+        let event = try!(create_user_event(context.core_as_ref()));
+
+        Ok(UserEvent {
+            event: event,
+            is_set: AtomicBool::new(false),
+            complete_on_drop: false,
+        })
+    }
+
+    /// Sets the status of this event to complete, allowing commands waiting
+    /// on it to run.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if the status of this event has already been set.
+    pub fn set_complete(&self) -> OclResult<()> {
+        self.set_status(ffi::CL_COMPLETE)
+    }
+
+    /// Sets the status of this event to the error code, `code`, which must
+    /// be negative. Commands waiting on this event will be terminated.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if `code` is not negative or if the status of this
+    /// event has already been set.
+    pub fn set_error(&self, code: i32) -> OclResult<()> {
+        if code >= 0 {
+            return OclError::err(format!("ocl::UserEvent::set_error(): Invalid error code: {}. \
+                Error codes must be negative.", code));
+        }
+        self.set_status(code)
+    }
+
+    /// Causes this event to be set to complete, rather than to an error
+    /// status, if it is dropped without having been set.
+    pub fn set_complete_on_drop(&mut self, complete_on_drop: bool) {
+        self.complete_on_drop = complete_on_drop;
+    }
+
+    /// Returns true if the status of this event has been set.
+    pub fn is_set(&self) -> bool {
+        self.is_set.load(Ordering::SeqCst)
+    }
+
+    /// Returns a reference to the underlying event.
+    pub fn as_event(&self) -> &Event {
+        &self.event
+    }
+
+    fn set_status(&self, status: ffi::cl_int) -> OclResult<()> {
+        if self.is_set.swap(true, Ordering::SeqCst) {
+            return OclError::err("ocl::UserEvent: The status of this event has already been set.");
+        }
+
+        let errcode = unsafe { ffi::clSetUserEventStatus(*self.event.as_ptr_ptr(), status) };
+
+        if errcode != ffi::CL_SUCCESS {
+            // Still unset, leaving it to be set again (or when dropped):
+            self.is_set.store(false, Ordering::SeqCst);
+            return OclError::err(format!("ocl::UserEvent: 'clSetUserEventStatus' failed with \
+                error code: {}.", errcode));
+        }
+
+        Ok(())
+    }
+}
+
+impl Deref for UserEvent {
+    type Target = Event;
+
+    fn deref(&self) -> &Event {
+        &self.event
+    }
+}
+
+impl AsRef<Event> for UserEvent {
+    fn as_ref(&self) -> &Event {
+        &self.event
+    }
+}
+
+impl std::fmt::Display for UserEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        self.event.fmt_info(f)
+    }
+}
+
+unsafe impl ClWaitList for UserEvent {
+    unsafe fn as_ptr_ptr(&self) -> *const ffi::cl_event {
+        self.event.as_ptr_ptr()
+    }
+
+    fn count(&self) -> u32 {
+        self.event.count()
+    }
+}
+
+impl Drop for UserEvent {
+    fn drop(&mut self) {
+        if !self.is_set() {
+            let status = if self.complete_on_drop { ffi::CL_COMPLETE } else { DROPPED_STATUS };
+            // Nothing can be done about an error here:
+            let _ = self.set_status(status);
+        }
+    }
+}
+
+
 
 /// A list of events for coordinating enqueued commands.
 ///
@@ -225,20 +601,28 @@ unsafe impl ClWaitList for Event {
 /// created them. Used to coordinate the activity of multiple commands with
 /// more fine-grained control than the queue alone.
 ///
-/// For access to individual events use `get_clone` and `last_clone` then
-/// either store or discard the result.
+/// Individual events can be accessed by index (`event_list[idx]`) or with
+/// `::iter`, which borrow them, or with `get_clone` and `last_clone`, which
+/// return new copies to be stored or discarded.
 ///
-// [FIXME] TODO: impl Index.
-#[derive(Debug, Clone)]
 pub struct EventList {
     event_list_core: EventListCore,
+    // Copies of the events in `event_list_core`, filled in as they are
+    // borrowed. Each is boxed so that references remain valid as more are
+    // added and is only removed through `&mut self`:
+    events: Mutex<Vec<Box<Event>>>,
 }
 
 impl EventList {
     /// Returns a new, empty, `EventList`.
     pub fn new() -> EventList {
+        EventList::from_core(EventListCore::new())
+    }
+
+    fn from_core(event_list_core: EventListCore) -> EventList {
         EventList {
-            event_list_core: EventListCore::new(),
+            event_list_core: event_list_core,
+            events: Mutex::new(Vec::new()),
         }
     }
 
@@ -258,7 +642,10 @@ impl EventList {
         //     },
         //     None => None,
         // }
-        self.event_list_core.pop().map(|ev| unsafe { Event::from_core(ev) })
+        let event = self.event_list_core.pop().map(|ev| unsafe { Event::from_core(ev) });
+        let len = self.event_list_core.len();
+        self.events.get_mut().unwrap().truncate(len);
+        event
     }
 
     // /// Appends a new null element to the end of the list and returns...
@@ -294,17 +681,43 @@ impl EventList {
         }
     }
 
+    /// Calls `callback` once the *last event* added to the list completes.
+    ///
+    /// See `Event::on_complete`.
+    pub fn on_complete<F>(&self, callback: F) -> OclResult<()>
+            where F: FnOnce(OclResult<CommandExecutionStatus>) + Send + 'static
+    {
+        self.on_status(CommandExecutionStatus::Complete, callback)
+    }
+
+    /// Calls `callback` once the *last event* added to the list reaches
+    /// `status` (or terminates abnormally).
+    ///
+    /// See `Event::on_status`.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if this list is empty or if the callback cannot be
+    /// set.
+    pub fn on_status<F>(&self, status: CommandExecutionStatus, callback: F) -> OclResult<()>
+            where F: FnOnce(OclResult<CommandExecutionStatus>) + Send + 'static
+    {
+        let event = try!(self.last_clone().ok_or(
+            OclError::new("ocl::EventList::on_status: This event list is empty.")));
+
+        event.on_status(status, callback)
+    }
+
     /// Sets a callback function, `callback_receiver`, to trigger upon completion of
     /// the *last event* added to the event list with an optional reference to user
     /// data.
     ///
+    /// Prefer `::on_complete` which takes a closure.
+    ///
     /// # Safety
     ///
     /// `user_data` must be guaranteed to still exist if and when `callback_receiver`
     /// is ever called.
-    ///
-    /// TODO: Create a safer type wrapper for `callback_receiver`.
-    /// TODO: Move this method to `Event`.
     pub unsafe fn set_callback<T>(&self,
                 callback_receiver: Option<EventCallbackFn>,
                 user_data: &mut T,
@@ -319,11 +732,13 @@ impl EventList {
 
     /// Clears all events from the list whether or not they have completed.
     pub fn clear(&mut self) -> OclResult<()> {
+        self.events.get_mut().unwrap().clear();
         self.event_list_core.clear()
     }
 
     /// Clears events which have completed.
     pub fn clear_completed(&mut self) -> OclResult<()> {
+        self.events.get_mut().unwrap().clear();
         self.event_list_core.clear_completed()
     }
 
@@ -344,9 +759,17 @@ impl EventList {
 
     // Returns a mutable reference to the underlying `core` event list.
     pub fn core_as_mut(&mut self) -> &mut EventListCore {
+        self.events.get_mut().unwrap().clear();
         &mut self.event_list_core
     }
 
+    /// Returns a future which resolves once all events currently in the list
+    /// have completed.
+    #[cfg(feature = "future")]
+    pub fn future(&self) -> EventListFuture {
+        EventListFuture::new(self.clone())
+    }
+
     /// Waits for all events in list to complete.
     pub fn wait(&self) -> OclResult<()> {
         if !self.event_list_core.is_empty() {
@@ -355,6 +778,206 @@ impl EventList {
             Ok(())
         }
     }
+
+    /// Returns an iterator over references to the events in the list.
+    pub fn iter(&self) -> EventListIter {
+        EventListIter { list: self, idx: 0 }
+    }
+
+    /// Returns the execution status of each event in the list or, for
+    /// events whose commands terminated abnormally, an error.
+    pub fn statuses(&self) -> Vec<OclResult<CommandExecutionStatus>> {
+        (0..self.len()).map(|idx| {
+            match self.get_clone(idx) {
+                Some(event) => execution_status_code(&event).and_then(status_from_code),
+                None => OclError::err("ocl::EventList::statuses(): Unable to retrieve event."),
+            }
+        }).collect()
+    }
+
+    /// Blocks until any event in the list completes (or terminates
+    /// abnormally) and returns its index. If several already have, the
+    /// lowest index is returned.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if the list is empty or if an event's status cannot
+    /// be determined.
+    pub fn wait_any(&self) -> OclResult<usize> {
+        if self.is_empty() {
+            return OclError::err("ocl::EventList::wait_any(): This event list is empty.");
+        }
+
+        let first = Arc::new((Mutex::new(None), Condvar::new()));
+
+        for idx in 0..self.len() {
+            let event = try!(self.clone_event(idx));
+            let first = first.clone();
+
+            // Called immediately if the event has already completed:
+            try!(event.on_complete(move |_| {
+                let &(ref first_idx, ref cvar) = &*first;
+                let mut first_idx = first_idx.lock().unwrap();
+                if first_idx.map(|first_idx| idx < first_idx).unwrap_or(true) {
+                    *first_idx = Some(idx);
+                }
+                cvar.notify_all();
+            }));
+        }
+
+        let &(ref first_idx, ref cvar) = &*first;
+        let mut first_idx = first_idx.lock().unwrap();
+
+        loop {
+            if let Some(idx) = *first_idx { return Ok(idx); }
+            first_idx = cvar.wait(first_idx).unwrap();
+        }
+    }
+
+    /// Blocks until every event in the list completes (or terminates
+    /// abnormally) or until `timeout` elapses. Returns true if every event
+    /// finished in time.
+    pub fn wait_timeout(&self, timeout: Duration) -> OclResult<bool> {
+        let deadline = Instant::now() + timeout;
+        let remaining = Arc::new((Mutex::new(self.len()), Condvar::new()));
+
+        for idx in 0..self.len() {
+            let event = try!(self.clone_event(idx));
+            let remaining = remaining.clone();
+
+            try!(event.on_complete(move |_| {
+                let &(ref count, ref cvar) = &*remaining;
+                *count.lock().unwrap() -= 1;
+                cvar.notify_all();
+            }));
+        }
+
+        let &(ref count, ref cvar) = &*remaining;
+        let mut count = count.lock().unwrap();
+
+        while *count > 0 {
+            let now = Instant::now();
+            if now >= deadline { return Ok(false); }
+            count = cvar.wait_timeout(count, deadline - now).unwrap().0;
+        }
+
+        Ok(true)
+    }
+
+    fn clone_event(&self, idx: usize) -> OclResult<Event> {
+        self.get_clone(idx).ok_or(OclError::new(format!("ocl::EventList: Unable to retrieve \
+            the event at index: {}.", idx)))
+    }
+}
+
+impl Index<usize> for EventList {
+    type Output = Event;
+
+    /// Returns a reference to the event at `idx`.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `idx` is out of bounds or if the event at `idx` is null (its
+    /// command failed to enqueue).
+    fn index(&self, idx: usize) -> &Event {
+        assert!(idx < self.len(), "ocl::EventList::index(): Index out of bounds (len: {}, \
+            index: {}).", self.len(), idx);
+
+        let mut events = self.events.lock().unwrap();
+
+        while events.len() <= idx {
+            let event = self.get_clone(events.len()).expect("ocl::EventList::index(): \
+                Unable to retrieve event.");
+            events.push(Box::new(event));
+        }
+
+        // Boxed events are never moved and are only dropped through
+        // `&mut self`, so they outlive this borrow:
+        unsafe { &*(&*events[idx] as *const Event) }
+    }
+}
+
+impl<'a> IntoIterator for &'a EventList {
+    type Item = &'a Event;
+    type IntoIter = EventListIter<'a>;
+
+    fn into_iter(self) -> EventListIter<'a> {
+        self.iter()
+    }
+}
+
+impl IntoIterator for EventList {
+    type Item = Event;
+    type IntoIter = std::vec::IntoIter<Event>;
+
+    fn into_iter(mut self) -> std::vec::IntoIter<Event> {
+        let mut events = Vec::with_capacity(self.len());
+        while let Some(event) = self.pop() { events.push(event); }
+        events.reverse();
+        events.into_iter()
+    }
+}
+
+impl Extend<Event> for EventList {
+    fn extend<I: IntoIterator<Item = Event>>(&mut self, iter: I) {
+        for event in iter {
+            self.push(event);
+        }
+    }
+}
+
+impl FromIterator<Event> for EventList {
+    fn from_iter<I: IntoIterator<Item = Event>>(iter: I) -> EventList {
+        let mut list = EventList::new();
+        list.extend(iter);
+        list
+    }
+}
+
+
+/// An iterator over references to the events in an `EventList`.
+///
+/// Created by `EventList::iter`.
+#[derive(Debug)]
+pub struct EventListIter<'a> {
+    list: &'a EventList,
+    idx: usize,
+}
+
+impl<'a> Iterator for EventListIter<'a> {
+    type Item = &'a Event;
+
+    fn next(&mut self) -> Option<&'a Event> {
+        let list = self.list;
+
+        if self.idx < list.len() {
+            self.idx += 1;
+            Some(&list[self.idx - 1])
+        } else {
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.list.len() - self.idx;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'a> ExactSizeIterator for EventListIter<'a> {}
+
+impl Clone for EventList {
+    fn clone(&self) -> EventList {
+        EventList::from_core(self.event_list_core.clone())
+    }
+}
+
+impl std::fmt::Debug for EventList {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("EventList")
+            .field("event_list_core", &self.event_list_core)
+            .finish()
+    }
 }
 
 impl Into<EventListCore> for EventList {
@@ -379,7 +1002,7 @@ impl Deref for EventList {
 
 impl DerefMut for EventList {
     fn deref_mut(&mut self) -> &mut EventListCore {
-        &mut self.event_list_core
+        self.core_as_mut()
     }
 }
 
@@ -0,0 +1,209 @@
+//! `std::future::Future` implementations for events and enqueued commands.
+//!
+//! Futures are woken from `OpenCL` completion callbacks and can be driven by
+//! any executor. Requires the `future` feature (and Rust 1.36 or later).
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context as TaskContext, Poll, Waker};
+use ffi;
+use core::OclPrm;
+use core::error::{Error as OclError, Result as OclResult};
+use standard::{Event, EventList};
+use standard::event::{execution_status_code, status_from_code};
+
+
+/// Wakes the most recently polling task once an event completes.
+///
+/// A single completion callback is registered with the event the first time
+/// it is polled; later polls only replace the waker it will use.
+#[derive(Debug, Default)]
+struct EventWaker {
+    waker: Arc<Mutex<Option<Waker>>>,
+    registered: bool,
+}
+
+impl EventWaker {
+    /// Polls `event`, arranging for the current task to be woken once it
+    /// completes (or fails) if it has not already.
+    fn poll(&mut self, event: &Event, cx: &mut TaskContext) -> Poll<OclResult<()>> {
+        // Stored before checking the status so that a completion racing with
+        // this poll still wakes the task:
+        *self.waker.lock().unwrap() = Some(cx.waker().clone());
+
+        if !self.registered {
+            let waker = self.waker.clone();
+
+            // Called immediately if the event has already completed:
+            if let Err(err) = event.on_complete(move |_| {
+                if let Some(waker) = waker.lock().unwrap().take() { waker.wake() }
+            }) {
+                return Poll::Ready(Err(err));
+            }
+
+            self.registered = true;
+        }
+
+        match execution_status_code(event) {
+            Ok(ffi::CL_COMPLETE) => Poll::Ready(Ok(())),
+            Ok(code) if code < 0 => Poll::Ready(status_from_code(code).map(|_| ())),
+            Ok(_) => Poll::Pending,
+            Err(err) => Poll::Ready(Err(err)),
+        }
+    }
+}
+
+// A clone must register its own callback rather than share the waker slot:
+impl Clone for EventWaker {
+    fn clone(&self) -> EventWaker {
+        EventWaker::default()
+    }
+}
+
+
+/// A future which resolves once the command associated with an event
+/// completes, or to an error if it terminates abnormally (or the event is
+/// empty).
+///
+/// Created by `Event::future`.
+#[derive(Debug, Clone)]
+pub struct EventFuture {
+    event: Event,
+    waker: EventWaker,
+}
+
+impl EventFuture {
+    /// Returns a new future waiting on `event`.
+    pub fn new(event: Event) -> EventFuture {
+        EventFuture { event: event, waker: EventWaker::default() }
+    }
+}
+
+impl Future for EventFuture {
+    type Output = OclResult<()>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut TaskContext) -> Poll<OclResult<()>> {
+        let this = &mut *self;
+        this.waker.poll(&this.event, cx)
+    }
+}
+
+
+/// A future which resolves once every event in an `EventList` has completed,
+/// or to an error as soon as one is found to have terminated abnormally.
+///
+/// Created by `EventList::future`.
+#[derive(Debug, Clone)]
+pub struct EventListFuture {
+    events: EventList,
+    completed: usize,
+    waker: EventWaker,
+}
+
+impl EventListFuture {
+    /// Returns a new future waiting on each of the events in `events`.
+    pub fn new(events: EventList) -> EventListFuture {
+        EventListFuture { events: events, completed: 0, waker: EventWaker::default() }
+    }
+}
+
+impl Future for EventListFuture {
+    type Output = OclResult<()>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut TaskContext) -> Poll<OclResult<()>> {
+        // Events before `completed` are known to have completed:
+        while self.completed < self.events.len() {
+            let event = match self.events.get_clone(self.completed) {
+                Some(event) => event,
+                None => return Poll::Ready(OclError::err("ocl::EventListFuture: Unable to \
+                    retrieve event.")),
+            };
+
+            match self.waker.poll(&event, cx) {
+                Poll::Ready(Ok(())) => {
+                    // The next event needs a callback of its own:
+                    self.completed += 1;
+                    self.waker = EventWaker::default();
+                },
+                other => return other,
+            }
+        }
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+
+/// A future which resolves to the contents of a buffer once a non-blocking
+/// read completes.
+///
+/// Created by `Buffer::read_async` and `SubBuffer::read_async`. Dropping the
+/// future before the read has completed blocks until it has.
+#[derive(Debug)]
+pub struct ReadFuture<T: OclPrm> {
+    data: Option<Vec<T>>,
+    event: Event,
+    waker: EventWaker,
+    enqueue_err: Option<OclError>,
+}
+
+impl<T: OclPrm> ReadFuture<T> {
+    /// Returns a new future for a read into `data`, enqueued with the result
+    /// `enqueue_result` and the event `event`.
+    ///
+    /// ## Safety
+    ///
+    /// The command associated with `event` must be the only command writing
+    /// to `data`.
+    pub unsafe fn new(data: Vec<T>, event: Event, enqueue_result: OclResult<()>)
+            -> ReadFuture<T>
+    {
+        ReadFuture {
+            data: Some(data),
+            event: event,
+            waker: EventWaker::default(),
+            enqueue_err: enqueue_result.err(),
+        }
+    }
+}
+
+// The future is never structurally pinned:
+impl<T: OclPrm> Unpin for ReadFuture<T> {}
+
+impl<T: OclPrm> Future for ReadFuture<T> {
+    type Output = OclResult<Vec<T>>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut TaskContext) -> Poll<OclResult<Vec<T>>> {
+        if let Some(err) = self.enqueue_err.take() {
+            self.data = None;
+            return Poll::Ready(Err(err));
+        }
+
+        let poll = {
+            let this = &mut *self;
+            this.waker.poll(&this.event, cx)
+        };
+
+        match poll {
+            Poll::Ready(Ok(())) => {
+                Poll::Ready(Ok(self.data.take().expect("ocl::ReadFuture::poll(): \
+                    Polled after completion.")))
+            },
+            Poll::Ready(Err(err)) => {
+                self.data = None;
+                Poll::Ready(Err(err))
+            },
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+impl<T: OclPrm> Drop for ReadFuture<T> {
+    fn drop(&mut self) {
+        // The device may still be writing to `data`:
+        if self.data.is_some() && !self.event.is_empty() {
+            let _ = self.event.wait();
+        }
+    }
+}
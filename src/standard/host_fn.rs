@@ -0,0 +1,304 @@
+//! Host functions (closures) enqueued as commands.
+//!
+//! Uses `clEnqueueNativeKernel` on devices with the `EXEC_NATIVE_KERNEL`
+//! capability. Elsewhere, the function is run from the completion callback
+//! of a marker command and a user event stands in for the command.
+
+use std;
+use std::panic::{self, AssertUnwindSafe};
+use libc::c_void;
+use ffi::{self, cl_command_queue, cl_event, cl_int, cl_mem};
+use core::{self, Mem as MemCore, ClEventPtrNew, ClWaitList, CommandExecutionStatus, DeviceInfo,
+    DeviceInfoResult, EXEC_NATIVE_KERNEL};
+use core::error::{Error as OclError, Result as OclResult};
+use standard::{Queue, Event, EventList};
+use standard::event::create_user_event;
+
+
+/// A host function, called with the host pointer of each memory object.
+///
+/// Called at most once.
+pub type HostFn = Box<FnMut(&[*mut c_void]) + Send>;
+
+/// Boxes `f`.
+pub fn host_fn<F>(f: F) -> HostFn where F: FnOnce(&[*mut c_void]) + Send + 'static {
+    let mut f = Some(f);
+    Box::new(move |host_ptrs: &[*mut c_void]| {
+        if let Some(f) = f.take() { f(host_ptrs) }
+    })
+}
+
+/// Returns true if the device associated with `queue` can execute native
+/// kernels.
+pub fn supports_native_kernels(queue: &Queue) -> bool {
+    match queue.device().info(DeviceInfo::ExecutionCapabilities) {
+        DeviceInfoResult::ExecutionCapabilities(caps) => caps.contains(EXEC_NATIVE_KERNEL),
+        _ => false,
+    }
+}
+
+/// Enqueues `f` to run after the events in `ewait` (and, for in-order
+/// queues, every previously enqueued command). Each memory object in `mems`
+/// (with its size in bytes) is mapped for reading and writing while `f`
+/// runs.
+pub fn enqueue(queue: &Queue, mems: &[(&MemCore, usize)], f: HostFn,
+        ewait: Option<&ClWaitList>, enew: Option<&mut ClEventPtrNew>) -> OclResult<()>
+{
+    if supports_native_kernels(queue) {
+        enqueue_native(queue, mems, f, ewait, enew)
+    } else {
+        enqueue_fallback(queue, mems, f, ewait, enew)
+    }
+}
+
+fn enqueue_native(queue: &Queue, mems: &[(&MemCore, usize)], f: HostFn,
+        ewait: Option<&ClWaitList>, enew: Option<&mut ClEventPtrNew>) -> OclResult<()>
+{
+    let (wait_count, wait_ptr) = wait_list(ewait);
+    let new_event_ptr = try!(new_event(enew));
+
+    // The argument block, copied by the runtime: the function pointer, the
+    // memory object count, then one host pointer per memory object (filled
+    // in by the runtime):
+    let f_ptr = Box::into_raw(Box::new(f)) as *mut c_void;
+    let mut args: Vec<*mut c_void> = vec![f_ptr, mems.len() as *mut c_void];
+    args.extend(mems.iter().map(|_| 0 as *mut c_void));
+
+    let mem_list: Vec<cl_mem> = mems.iter().map(|&(mem, _)| mem.as_ptr()).collect();
+    let mem_locs: Vec<*const c_void> = (0..mems.len())
+        .map(|mem_idx| &args[2 + mem_idx] as *const *mut c_void as *const c_void)
+        .collect();
+
+    // If an event in the wait list fails, the runtime never calls
+    // `run_native` and gives no notice of it, so `f` cannot be dropped (see
+    // `Queue::enqueue_host_fn`):
+    let errcode = unsafe { ffi::clEnqueueNativeKernel(
+        queue.core_as_ref().as_ptr(),
+        Some(run_native),
+        args.as_mut_ptr() as *mut c_void,
+        args.len() * std::mem::size_of::<*mut c_void>(),
+        mem_list.len() as u32,
+        if mem_list.is_empty() { 0 as *const cl_mem } else { mem_list.as_ptr() },
+        if mem_locs.is_empty() { 0 as *const *const c_void } else { mem_locs.as_ptr() },
+        wait_count,
+        wait_ptr,
+        new_event_ptr,
+    ) };
+
+    if errcode != ffi::CL_SUCCESS {
+        unsafe { drop(Box::from_raw(f_ptr as *mut HostFn)); }
+        return errcode_err("clEnqueueNativeKernel", errcode);
+    }
+
+    Ok(())
+}
+
+extern "C" fn run_native(args: *mut c_void) {
+    unsafe {
+        let args = args as *const *mut c_void;
+        let f = Box::from_raw(*args as *mut HostFn);
+        let mem_count = *args.offset(1) as usize;
+        call(f, std::slice::from_raw_parts(args.offset(2), mem_count));
+    }
+}
+
+
+/// The state passed to `run_fallback`.
+struct Fallback {
+    f: HostFn,
+    host_ptrs: Vec<*mut c_void>,
+    user_event: Event,
+    status_event: Option<Event>,
+}
+
+fn enqueue_fallback(queue: &Queue, mems: &[(&MemCore, usize)], f: HostFn,
+        ewait: Option<&ClWaitList>, enew: Option<&mut ClEventPtrNew>) -> OclResult<()>
+{
+    let queue_ptr = queue.core_as_ref().as_ptr();
+    let (wait_count, wait_ptr) = wait_list(ewait);
+    let mut errcode: cl_int = 0;
+
+    // Map each memory object once the wait list completes:
+    let mut map_events = EventList::new();
+    let mut host_ptrs = Vec::with_capacity(mems.len());
+
+    for &(mem, size) in mems.iter() {
+        let mut map_event = Event::empty();
+        let host_ptr = unsafe { ffi::clEnqueueMapBuffer(queue_ptr, mem.as_ptr(), ffi::CL_FALSE,
+            ffi::CL_MAP_READ | ffi::CL_MAP_WRITE, 0, size, wait_count, wait_ptr,
+            try!(map_event.ptr_mut_ptr_new()), &mut errcode) };
+
+        if errcode != ffi::CL_SUCCESS {
+            unmap(queue_ptr, &mems[..host_ptrs.len()], &host_ptrs, &map_events);
+            return errcode_err("clEnqueueMapBuffer", errcode);
+        }
+
+        map_events.push(map_event);
+        host_ptrs.push(host_ptr);
+    }
+
+    // The marker completes once the wait list (or the mapping) completes:
+    let mut marker = Event::empty();
+    let (marker_wait_count, marker_wait_ptr) = if mems.is_empty() {
+        (wait_count, wait_ptr)
+    } else {
+        (map_events.count(), unsafe { map_events.as_ptr_ptr() })
+    };
+
+    errcode = unsafe { ffi::clEnqueueMarkerWithWaitList(queue_ptr, marker_wait_count,
+        marker_wait_ptr, try!(marker.ptr_mut_ptr_new())) };
+
+    if errcode != ffi::CL_SUCCESS {
+        unmap(queue_ptr, mems, &host_ptrs, &map_events);
+        return errcode_err("clEnqueueMarkerWithWaitList", errcode);
+    }
+
+    // Stands in for the host function's command:
+    let user_event = match create_user_event(queue.context_core_as_ref()) {
+        Ok(user_event) => user_event,
+        Err(err) => {
+            unmap(queue_ptr, mems, &host_ptrs, &map_events);
+            return Err(err);
+        },
+    };
+
+    // Unmap each memory object once the host function completes. The
+    // callback is registered only once everything else has been enqueued.
+    // Until then, a failure completes the user event without calling `f`,
+    // so that every memory object is still unmapped:
+    let mut unmap_events = EventList::new();
+
+    for (idx, (&(mem, _), &host_ptr)) in mems.iter().zip(host_ptrs.iter()).enumerate() {
+        let mut unmap_event = Event::empty();
+        errcode = unsafe { ffi::clEnqueueUnmapMemObject(queue_ptr, mem.as_ptr(), host_ptr,
+            user_event.count(), user_event.as_ptr_ptr(), try!(unmap_event.ptr_mut_ptr_new())) };
+
+        if errcode != ffi::CL_SUCCESS {
+            unmap(queue_ptr, &mems[idx..], &host_ptrs[idx..], &map_events);
+            set_user_event_status(&user_event, ffi::CL_COMPLETE);
+            return errcode_err("clEnqueueUnmapMemObject", errcode);
+        }
+
+        unmap_events.push(unmap_event);
+    }
+
+    // The unmaps must run even if a waited event fails, so the failure is
+    // instead reported through a second user event waited on by the
+    // barrier:
+    let status_event = if mems.is_empty() {
+        None
+    } else {
+        match create_user_event(queue.context_core_as_ref()) {
+            Ok(status_event) => {
+                unmap_events.push(status_event.clone());
+                Some(status_event)
+            },
+            Err(err) => {
+                set_user_event_status(&user_event, ffi::CL_COMPLETE);
+                return Err(err);
+            },
+        }
+    };
+
+    // Commands enqueued later wait for the host function (and unmapping):
+    let (barrier_wait_count, barrier_wait_ptr) = if mems.is_empty() {
+        (user_event.count(), unsafe { user_event.as_ptr_ptr() })
+    } else {
+        (unmap_events.count(), unsafe { unmap_events.as_ptr_ptr() })
+    };
+
+    errcode = unsafe { ffi::clEnqueueBarrierWithWaitList(queue_ptr, barrier_wait_count,
+        barrier_wait_ptr, try!(new_event(enew))) };
+
+    if errcode != ffi::CL_SUCCESS {
+        set_fallback_status(&user_event, status_event.as_ref(), ffi::CL_COMPLETE);
+        return errcode_err("clEnqueueBarrierWithWaitList", errcode);
+    }
+
+    let state = Box::into_raw(Box::new(Fallback {
+        f: f,
+        host_ptrs: host_ptrs,
+        user_event: user_event.clone(),
+        status_event: status_event.clone(),
+    }));
+
+    if let Err(err) = core::set_event_callback(marker.core_as_ref().unwrap(),
+            CommandExecutionStatus::Complete, Some(run_fallback), state as *mut c_void) {
+        unsafe { drop(Box::from_raw(state)); }
+        set_fallback_status(&user_event, status_event.as_ref(), ffi::CL_COMPLETE);
+        return Err(err);
+    }
+
+    Ok(())
+}
+
+/// Enqueues an unmapping of each memory object in `mems` from the
+/// corresponding host pointer once the events in `ewait` complete. Used to
+/// clean up after a failure, so errors are ignored.
+fn unmap(queue_ptr: cl_command_queue, mems: &[(&MemCore, usize)], host_ptrs: &[*mut c_void],
+        ewait: &EventList)
+{
+    let (wait_count, wait_ptr) = wait_list(Some(ewait as &ClWaitList));
+
+    for (&(mem, _), &host_ptr) in mems.iter().zip(host_ptrs.iter()) {
+        unsafe { ffi::clEnqueueUnmapMemObject(queue_ptr, mem.as_ptr(), host_ptr, wait_count,
+            wait_ptr, 0 as *mut cl_event); }
+    }
+}
+
+extern "C" fn run_fallback(_: cl_event, status: cl_int, state: *mut c_void) {
+    let state = unsafe { Box::from_raw(state as *mut Fallback) };
+    let Fallback { f, host_ptrs, user_event, status_event } = *state;
+
+    // A negative status indicates that a command waited on failed, in which
+    // case `f` is not called:
+    if status >= 0 {
+        call(Box::new(f), &host_ptrs);
+    }
+
+    set_fallback_status(&user_event, status_event.as_ref(),
+        if status < 0 { status } else { ffi::CL_COMPLETE });
+}
+
+/// Completes `user_event`, allowing any unmaps to run, and sets `status` on
+/// `status_event` (or on `user_event` if there are no memory objects).
+fn set_fallback_status(user_event: &Event, status_event: Option<&Event>, status: cl_int) {
+    match status_event {
+        Some(status_event) => {
+            set_user_event_status(user_event, ffi::CL_COMPLETE);
+            set_user_event_status(status_event, status);
+        },
+        None => set_user_event_status(user_event, status),
+    }
+}
+
+fn set_user_event_status(user_event: &Event, status: cl_int) {
+    // Nothing can be done about an error here (within a callback):
+    unsafe { ffi::clSetUserEventStatus(*user_event.as_ptr_ptr(), status); }
+}
+
+
+/// Calls `f`. Unwinding into the OpenCL runtime is undefined behavior so
+/// panics are caught (their messages will already have been printed).
+fn call(mut f: Box<HostFn>, host_ptrs: &[*mut c_void]) {
+    let _ = panic::catch_unwind(AssertUnwindSafe(|| f(host_ptrs)));
+}
+
+fn wait_list(ewait: Option<&ClWaitList>) -> (u32, *const cl_event) {
+    match ewait {
+        Some(wl) if wl.count() > 0 => (wl.count(), unsafe { wl.as_ptr_ptr() }),
+        _ => (0, 0 as *const cl_event),
+    }
+}
+
+fn new_event(enew: Option<&mut ClEventPtrNew>) -> OclResult<*mut cl_event> {
+    match enew {
+        Some(enew) => enew.ptr_mut_ptr_new(),
+        None => Ok(0 as *mut cl_event),
+    }
+}
+
+fn errcode_err(fn_name: &str, errcode: cl_int) -> OclResult<()> {
+    OclError::err(format!("ocl::Queue::enqueue_host_fn(): '{}' failed with error code: {}.",
+        fn_name, errcode))
+}
@@ -9,10 +9,47 @@ use std::convert::Into;
 use core::error::{Error as OclError, Result as OclResult};
 use core::{self, OclPrm, Mem as MemCore, MemFlags, MemObjectType, ImageFormat, ImageDescriptor,
     ImageInfo, ImageInfoResult, MemInfo, MemInfoResult, ClEventPtrNew, ClWaitList,
-    ImageChannelOrder, ImageChannelDataType, GlTextureTarget};
-use standard::{Context, Queue, MemLen, SpatialDims, AsMemRef};
+    ImageChannelOrder, ImageChannelDataType, GlTextureTarget, KernelArg};
+use standard::{Context, Queue, MemLen, SpatialDims, AsMemRef, PixelConverter, Kernel,
+    KernelArgValue, Resource};
+use standard::tracker::copy_host_data;
+use standard::pixel;
 use ffi::{cl_GLuint, cl_GLint};
 
+/// Returns the number of pixels within `region`.
+fn region_pixel_count(region: [usize; 3]) -> usize {
+    region[0] * region[1] * region[2]
+}
+
+/// Returns a converter for the format of `image` after verifying that
+/// `data_len` is exactly four components per pixel within `region`.
+fn pixel_converter(image: &MemCore, format: Option<&ImageFormat>, region: [usize; 3],
+        data_len: usize) -> OclResult<PixelConverter>
+{
+    if data_len != region_pixel_count(region) * 4 {
+        return OclError::err(format!("ocl::ImageCmd::enq(): The float data length ({}) must \
+            be exactly four components (RGBA) for each pixel in the region ({:?} = {} \
+            pixels).", data_len, region, region_pixel_count(region)));
+    }
+
+    image_converter(image, format)
+}
+
+/// Returns a converter for `format` or, if `None`, the queried format of
+/// `image`.
+fn image_converter(image: &MemCore, format: Option<&ImageFormat>) -> OclResult<PixelConverter> {
+    // Images cache their format. Otherwise it must be queried:
+    if let Some(format) = format {
+        return PixelConverter::new(format.clone());
+    }
+
+    match core::get_image_info(image, ImageInfo::Format) {
+        ImageInfoResult::Format(format) => PixelConverter::new(format),
+        ImageInfoResult::Error(err) => Err(*err),
+        _ => OclError::err("ocl::ImageCmd::enq(): Unexpected 'ImageInfoResult' variant."),
+    }
+}
+
 /// A builder for `Image`.
 pub struct ImageBuilder<S: OclPrm> {
     flags: MemFlags,
@@ -293,6 +330,8 @@ pub enum ImageCmdKind<'b, E: 'b> {
     Unspecified,
     Read { data: &'b mut [E] },
     Write { data: &'b [E] },
+    ReadF32 { data: &'b mut [f32] },
+    WriteF32 { data: &'b [f32] },
     Fill { color: E },
     Copy { dst_image: &'b MemCore, dst_origin: [usize; 3] },
     CopyToBuffer { buffer: &'b MemCore, dst_origin: usize },
@@ -329,9 +368,11 @@ impl<'b, E: 'b> ImageCmdKind<'b, E> {
 /// // Reads without blocking:
 /// image.cmd().read_async(&dst_vec).enew(&empty_event).enq().unwrap();
 ///
+/// // Fills the second layer of a 2d image array:
+/// image.layer(1).unwrap().fill(color).enq().unwrap();
+///
 /// ```
 ///
-/// [FIXME]: Fills not yet implemented.
 #[allow(dead_code)]
 pub struct ImageCmd<'b, E: 'b + OclPrm> {
     queue: &'b Queue,
@@ -346,6 +387,7 @@ pub struct ImageCmd<'b, E: 'b + OclPrm> {
     ewait: Option<&'b ClWaitList>,
     enew: Option<&'b mut ClEventPtrNew>,
     mem_dims: [usize; 3],
+    format: Option<&'b ImageFormat>,
 }
 
 /// [UNSTABLE]: All methods still in a state of adjustifulsomeness.
@@ -369,6 +411,7 @@ impl<'b, E: 'b + OclPrm> ImageCmd<'b, E> {
             ewait: None,
             enew: None,
             mem_dims: dims,
+            format: None,
         }
     }
 
@@ -503,6 +546,53 @@ impl<'b, E: 'b + OclPrm> ImageCmd<'b, E> {
         self
     }
 
+    /// Specifies that this command will be a blocking read operation,
+    /// converting pixels from the image's format into four component (RGBA)
+    /// floats.
+    ///
+    /// `dst_data` must contain exactly four components for each pixel in the
+    /// region. See `PixelConverter` for details about conversion. Commands
+    /// created from an `Image` use the format cached in its properties.
+    ///
+    /// Because pixels are converted after the read completes, the blocking
+    /// state of this command is locked to true.
+    ///
+    /// ## Panics
+    ///
+    /// The command operation kind must not have already been specified.
+    ///
+    pub fn read_f32(mut self, dst_data: &'b mut [f32]) -> ImageCmd<'b, E> {
+        assert!(self.kind.is_unspec(), "ocl::ImageCmd::read_f32(): Operation kind \
+            already set for this command.");
+        self.kind = ImageCmdKind::ReadF32 { data: dst_data };
+        self.block = true;
+        self.lock_block = true;
+        self
+    }
+
+    /// Specifies that this command will be a blocking write operation,
+    /// converting four component (RGBA) floats into the image's format.
+    ///
+    /// `src_data` must contain exactly four components for each pixel in the
+    /// region. See `PixelConverter` for details about conversion. Commands
+    /// created from an `Image` use the format cached in its properties.
+    ///
+    /// Because converted pixels are staged in a temporary host buffer, the
+    /// blocking state of this command is locked to true.
+    ///
+    /// ## Panics
+    ///
+    /// The command operation kind must not have already been specified.
+    ///
+    pub fn write_f32(mut self, src_data: &'b [f32]) -> ImageCmd<'b, E> {
+        assert!(self.kind.is_unspec(), "ocl::ImageCmd::write_f32(): Operation kind \
+            already set for this command.");
+        self.kind = ImageCmdKind::WriteF32 { data: src_data };
+        self.block = true;
+        self.lock_block = true;
+        self
+    }
+
     /// Specifies that this command will be a copy operation.
     ///
     /// If `.block(..)` has been set it will be ignored.
@@ -574,10 +664,22 @@ impl<'b, E: 'b + OclPrm> ImageCmd<'b, E> {
     ///
     /// If `.block(..)` has been set it will be ignored.
     ///
+    /// `color` is given as stored in the image: if `E` is a scalar type, such
+    /// as `u8` or `i32`, it is repeated for each channel of a pixel. Vector
+    /// types, such as `ClUchar4` or `ClInt4`, specify a whole pixel. When
+    /// enqueued, the pixel is converted to the four component `float4`,
+    /// `int4`, or `uint4` color expected for the image's channel data type
+    /// (see the [SDK docs]). For example, `255u8` fills a `UnormInt8` image
+    /// with `1.0`.
+    ///
+    /// Enqueuing returns an error if the size of `E` does not evenly divide
+    /// the size of a pixel.
+    ///
     /// ## Panics
     ///
     /// The command operation kind must not have already been specified
     ///
+    /// [SDK docs]: https://www.khronos.org/registry/cl/sdk/1.2/docs/man/xhtml/clEnqueueFillImage.html
     pub fn fill(mut self, color: E) -> ImageCmd<'b, E> {
         assert!(self.kind.is_unspec(), "ocl::ImageCmd::fill(): Operation kind \
             already set for this command.");
@@ -616,32 +718,144 @@ impl<'b, E: 'b + OclPrm> ImageCmd<'b, E> {
     ///
     /// TODO: FOR COPY, FILL, AND COPYTOBUFFER -- ENSURE PITCHES ARE BOTH UNSET.
     pub fn enq(self) -> OclResult<()> {
-        match self.kind {
-            ImageCmdKind::Read { data } => {
-                // try!(check_len(self.to_len, data.len(), offset));
-                unsafe { core::enqueue_read_image(self.queue, self.obj_core, self.block,
-                    self.origin, self.region, self.row_pitch, self.slc_pitch, data, self.ewait,
-                    self.enew) }
-            },
-            ImageCmdKind::Write { data } => {
-                core::enqueue_write_image(self.queue, self.obj_core, self.block,
-                    self.origin, self.region, self.row_pitch, self.slc_pitch, data, self.ewait,
-                    self.enew)
-            },
-            ImageCmdKind::Copy { dst_image, dst_origin } => {
-                core::enqueue_copy_image::<E>(self.queue, self.obj_core, dst_image, self.origin,
-                    dst_origin, self.region, self.ewait, self.enew)
-            },
-            ImageCmdKind::GLAcquire => {
-                core::enqueue_acquire_gl_buffer(self.queue, self.obj_core, self.ewait, self.enew)
-            },
-            ImageCmdKind::GLRelease => {
-                core::enqueue_release_gl_buffer(self.queue, self.obj_core, self.ewait, self.enew)
-            },
-            ImageCmdKind::Unspecified => OclError::err("ocl::ImageCmd::enq(): No operation \
-                specified. Use '.read(...)', 'write(...)', etc. before calling '.enq()'."),
-            _ => unimplemented!(),
+        let ImageCmd { queue, obj_core, block, origin, region, row_pitch, slc_pitch, kind, ewait,
+            enew, format, .. } = self;
+
+        // Retain the images until the command completes:
+        let mut resources = vec![Resource::Mem(obj_core.clone())];
+        if let ImageCmdKind::Copy { dst_image, .. } = kind {
+            resources.push(Resource::Mem(dst_image.clone()));
         }
+
+        // Non-blocking writes read from a retained copy of the data:
+        let kind = match kind {
+            ImageCmdKind::Write { data } if !block => {
+                ImageCmdKind::Write { data: unsafe { copy_host_data(data, &mut resources) } }
+            },
+            kind => kind,
+        };
+
+        queue.tracker().enqueue(resources, enew, |enew| {
+            match kind {
+                ImageCmdKind::Read { data } => {
+                    // try!(check_len(self.to_len, data.len(), offset));
+                    unsafe { core::enqueue_read_image(queue, obj_core, block,
+                        origin, region, row_pitch, slc_pitch, data, ewait,
+                        enew) }
+                },
+                ImageCmdKind::Write { data } => {
+                    core::enqueue_write_image(queue, obj_core, block,
+                        origin, region, row_pitch, slc_pitch, data, ewait,
+                        enew)
+                },
+                ImageCmdKind::ReadF32 { data } => {
+                    let converter = try!(pixel_converter(obj_core, format, region, data.len()));
+                    let mut bytes = vec![0u8; region_pixel_count(region) *
+                        converter.pixel_size()];
+                    unsafe { try!(core::enqueue_read_image(queue, obj_core, true,
+                        origin, region, 0, 0, &mut bytes, ewait, enew)); }
+                    converter.unpack_into(&bytes, data)
+                },
+                ImageCmdKind::WriteF32 { data } => {
+                    let converter = try!(pixel_converter(obj_core, format, region, data.len()));
+                    let bytes = try!(converter.pack(data));
+                    core::enqueue_write_image(queue, obj_core, true,
+                        origin, region, 0, 0, &bytes, ewait, enew)
+                },
+                ImageCmdKind::Fill { color } => {
+                    let converter = try!(image_converter(obj_core, format));
+                    let color_size = mem::size_of::<E>();
+
+                    if converter.pixel_size() % color_size != 0 {
+                        return OclError::err(format!("ocl::ImageCmd::enq(): Invalid fill color type. \
+                            The size of the fill color type ({} bytes) must evenly divide the size of \
+                            a pixel ({} bytes).", color_size, converter.pixel_size()));
+                    }
+
+                    // Repeated to form a single pixel then converted to the
+                    // 'float4', 'int4', or 'uint4' expected for the format:
+                    let color_bytes = unsafe {
+                        std::slice::from_raw_parts(&color as *const E as *const u8, color_size)
+                    };
+                    let pixel: Vec<u8> = color_bytes.iter().cycle().take(converter.pixel_size())
+                        .cloned().collect();
+                    let color = try!(pixel::fill_color(&converter, &pixel));
+
+                    core::enqueue_fill_image(queue, obj_core, &color, origin,
+                        region, ewait, enew, Some(&queue.device_version()))
+                },
+                ImageCmdKind::Copy { dst_image, dst_origin } => {
+                    core::enqueue_copy_image::<E>(queue, obj_core, dst_image, origin,
+                        dst_origin, region, ewait, enew)
+                },
+                ImageCmdKind::GLAcquire => {
+                    core::enqueue_acquire_gl_buffer(queue, obj_core, ewait, enew)
+                },
+                ImageCmdKind::GLRelease => {
+                    core::enqueue_release_gl_buffer(queue, obj_core, ewait, enew)
+                },
+                ImageCmdKind::Unspecified => OclError::err("ocl::ImageCmd::enq(): No operation \
+                    specified. Use '.read(...)', 'write(...)', etc. before calling '.enq()'."),
+                _ => unimplemented!(),
+            }
+        })
+    }
+}
+
+/// The properties of an `Image`, captured when it is created.
+///
+/// Available from `Image::properties` without querying the device. The row
+/// and slice pitches and element size are those reported by the
+/// implementation and may differ from those requested in `descriptor`.
+///
+#[derive(Clone, Debug)]
+pub struct ImageProperties {
+    pub format: ImageFormat,
+    pub descriptor: ImageDescriptor,
+    pub row_pitch: usize,
+    pub slice_pitch: usize,
+    pub element_size: usize,
+    pub flags: MemFlags,
+}
+
+impl ImageProperties {
+    /// Queries the format, pitches, and element size of `obj_core`.
+    fn new(obj_core: &MemCore, flags: MemFlags, descriptor: ImageDescriptor)
+            -> OclResult<ImageProperties>
+    {
+        let format = match core::get_image_info(obj_core, ImageInfo::Format) {
+            ImageInfoResult::Format(format) => format,
+            res => return unexpected_info_result(res),
+        };
+        let element_size = match core::get_image_info(obj_core, ImageInfo::ElementSize) {
+            ImageInfoResult::ElementSize(s) => s,
+            res => return unexpected_info_result(res),
+        };
+        let row_pitch = match core::get_image_info(obj_core, ImageInfo::RowPitch) {
+            ImageInfoResult::RowPitch(p) => p,
+            res => return unexpected_info_result(res),
+        };
+        let slice_pitch = match core::get_image_info(obj_core, ImageInfo::SlicePitch) {
+            ImageInfoResult::SlicePitch(p) => p,
+            res => return unexpected_info_result(res),
+        };
+
+        Ok(ImageProperties {
+            format: format,
+            descriptor: descriptor,
+            row_pitch: row_pitch,
+            slice_pitch: slice_pitch,
+            element_size: element_size,
+            flags: flags,
+        })
+    }
+}
+
+/// Returns the error contained in an error or unexpected info result.
+fn unexpected_info_result<T>(res: ImageInfoResult) -> OclResult<T> {
+    match res {
+        ImageInfoResult::Error(err) => Err(*err),
+        _ => OclError::err("ocl::ImageProperties::new(): Unexpected 'ImageInfoResult' variant."),
     }
 }
 
@@ -654,6 +868,7 @@ pub struct Image<E: OclPrm> {
     obj_core: MemCore,
     queue: Queue,
     dims: SpatialDims,
+    properties: ImageProperties,
     pixel_element_len: usize,
     _pixel: PhantomData<E>
 }
@@ -686,19 +901,15 @@ impl<E: OclPrm> Image<E> {
             Some(&[queue.device_version()]),
         )) };
 
-        let pixel_element_len = match core::get_image_info(&obj_core, ImageInfo::ElementSize) {
-            ImageInfoResult::ElementSize(s) => s / mem::size_of::<E>(),
-            ImageInfoResult::Error(err) => return Err(*err),
-            _ => return OclError::err("ocl::Image::element_len(): \
-                Unexpected 'ImageInfoResult' variant."),
-        };
-
         let dims = [image_desc.image_width, image_desc.image_height, image_desc.image_depth].into();
+        let properties = try!(ImageProperties::new(&obj_core, flags, image_desc));
+        let pixel_element_len = properties.element_size / mem::size_of::<E>();
 
         let new_img = Image {
             obj_core: obj_core,
             queue: queue,
             dims: dims,
+            properties: properties,
             pixel_element_len: pixel_element_len,
             _pixel: PhantomData,
         };
@@ -739,20 +950,15 @@ impl<E: OclPrm> Image<E> {
                                         texture,
                                         flags)) };
 
-        // FIXME can I do this from a GLTexture ?
-        let pixel_element_len = match core::get_image_info(&obj_core, ImageInfo::ElementSize) {
-            ImageInfoResult::ElementSize(s) => s / mem::size_of::<E>(),
-            ImageInfoResult::Error(err) => return Err(*err),
-            _ => return OclError::err("ocl::Image::element_len(): \
-                Unexpected 'ImageInfoResult' variant."),
-        };
-
         let dims = [image_desc.image_width, image_desc.image_height, image_desc.image_depth].into();
+        let properties = try!(ImageProperties::new(&obj_core, flags, image_desc));
+        let pixel_element_len = properties.element_size / mem::size_of::<E>();
 
         let new_img = Image {
             obj_core: obj_core,
             queue: queue,
             dims: dims,
+            properties: properties,
             pixel_element_len: pixel_element_len,
             _pixel: PhantomData,
         };
@@ -770,20 +976,15 @@ impl<E: OclPrm> Image<E> {
                                         renderbuffer,
                                         flags)) };
 
-        // FIXME can I do this from a renderbuffer ?
-        let pixel_element_len = match core::get_image_info(&obj_core, ImageInfo::ElementSize) {
-            ImageInfoResult::ElementSize(s) => s / mem::size_of::<E>(),
-            ImageInfoResult::Error(err) => return Err(*err),
-            _ => return OclError::err("ocl::Image::element_len(): \
-                Unexpected 'ImageInfoResult' variant."),
-        };
-
         let dims = [image_desc.image_width, image_desc.image_height].into();
+        let properties = try!(ImageProperties::new(&obj_core, flags, image_desc));
+        let pixel_element_len = properties.element_size / mem::size_of::<E>();
 
         let new_img = Image {
             obj_core: obj_core,
             queue: queue,
             dims: dims,
+            properties: properties,
             pixel_element_len: pixel_element_len,
             _pixel: PhantomData,
         };
@@ -796,8 +997,10 @@ impl<E: OclPrm> Image<E> {
     /// Run `.enq()` to enqueue the command.
     ///
     pub fn cmd(&self) -> ImageCmd<E> {
-        ImageCmd::new(&self.queue, &self.obj_core,
-            self.dims.to_lens().expect("ocl::Image::cmd"))
+        let mut cmd = ImageCmd::new(&self.queue, &self.obj_core,
+            self.dims.to_lens().expect("ocl::Image::cmd"));
+        cmd.format = Some(&self.properties.format);
+        cmd
     }
 
     /// Returns an image command builder set to read.
@@ -816,6 +1019,54 @@ impl<E: OclPrm> Image<E> {
         self.cmd().write(data)
     }
 
+    /// Returns an image command builder set to read, converting pixels into
+    /// four component (RGBA) floats.
+    ///
+    /// Run `.enq()` to enqueue the command.
+    ///
+    pub fn read_f32<'b>(&'b self, data: &'b mut [f32]) -> ImageCmd<'b, E> {
+        self.cmd().read_f32(data)
+    }
+
+    /// Returns an image command builder set to write, converting four
+    /// component (RGBA) floats into the image's format.
+    ///
+    /// Run `.enq()` to enqueue the command.
+    ///
+    pub fn write_f32<'b>(&'b self, data: &'b [f32]) -> ImageCmd<'b, E> {
+        self.cmd().write_f32(data)
+    }
+
+    /// Returns a handle to a single layer of a 1d or 2d image array.
+    ///
+    /// Commands created from the returned layer have their origin and region
+    /// set to cover exactly that layer.
+    ///
+    /// ## Errors
+    ///
+    /// This image must be an `Image1dArray` or `Image2dArray` and `layer_idx`
+    /// must be less than its array size.
+    ///
+    pub fn layer(&self, layer_idx: usize) -> OclResult<ImageLayer<E>> {
+        ImageLayer::new(self, layer_idx)
+    }
+
+    /// Returns a handle to each layer of a 1d or 2d image array, in order.
+    ///
+    /// ## Errors
+    ///
+    /// This image must be an `Image1dArray` or `Image2dArray`.
+    ///
+    pub fn layers(&self) -> OclResult<Vec<ImageLayer<E>>> {
+        match self.image_type() {
+            MemObjectType::Image1dArray | MemObjectType::Image2dArray => {
+                (0..self.array_size()).map(|layer_idx| ImageLayer::new(self, layer_idx)).collect()
+            },
+            image_type => OclError::err(format!("ocl::Image::layers(): Only 'Image1dArray' and \
+                'Image2dArray' images have layers (image type: {:?}).", image_type)),
+        }
+    }
+
     /// Changes the default queue.
     ///
     /// Returns a ref for chaining i.e.:
@@ -844,6 +1095,28 @@ impl<E: OclPrm> Image<E> {
         &self.dims
     }
 
+    /// Returns the properties (format, descriptor, pitches, etc.) this
+    /// image was created with.
+    pub fn properties(&self) -> &ImageProperties {
+        &self.properties
+    }
+
+    /// Returns the type of this image (`Image1d`, `Image2dArray`, etc.).
+    pub fn image_type(&self) -> MemObjectType {
+        self.properties.descriptor.image_type
+    }
+
+    /// Returns the number of images in this image array or zero if this is
+    /// not an image array.
+    pub fn array_size(&self) -> usize {
+        match self.image_type() {
+            MemObjectType::Image1dArray | MemObjectType::Image2dArray => {
+                self.properties.descriptor.image_array_size
+            },
+            _ => 0,
+        }
+    }
+
     /// Returns the total number of pixels in this image.
     pub fn pixel_count(&self) -> usize {
         self.dims.to_len()
@@ -885,17 +1158,22 @@ impl<E: OclPrm> Image<E> {
 
     /// Format image info.
     fn fmt_info(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let props = &self.properties;
+
         f.debug_struct("Image")
-            .field("ElementSize", &self.info(ImageInfo::ElementSize))
-            .field("RowPitch", &self.info(ImageInfo::RowPitch))
-            .field("SlicePitch", &self.info(ImageInfo::SlicePitch))
-            .field("Width", &self.info(ImageInfo::Width))
-            .field("Height", &self.info(ImageInfo::Height))
-            .field("Depth", &self.info(ImageInfo::Depth))
-            .field("ArraySize", &self.info(ImageInfo::ArraySize))
-            .field("Buffer", &self.info(ImageInfo::Buffer))
-            .field("NumMipLevels", &self.info(ImageInfo::NumMipLevels))
-            .field("NumSamples", &self.info(ImageInfo::NumSamples))
+            .field("ImageType", &props.descriptor.image_type)
+            .field("Format", &props.format)
+            .field("ElementSize", &props.element_size)
+            .field("RowPitch", &props.row_pitch)
+            .field("SlicePitch", &props.slice_pitch)
+            .field("Width", &props.descriptor.image_width)
+            .field("Height", &props.descriptor.image_height)
+            .field("Depth", &props.descriptor.image_depth)
+            .field("ArraySize", &props.descriptor.image_array_size)
+            .field("Buffer", &props.descriptor.buffer)
+            .field("NumMipLevels", &props.descriptor.num_mip_levels)
+            .field("NumSamples", &props.descriptor.num_samples)
+            .field("Flags", &props.flags)
             .finish()
     }
 
@@ -954,3 +1232,164 @@ impl<'a, T: OclPrm> AsMemRef<T> for &'a mut Image<T> {
         &self.obj_core
     }
 }
+
+impl<'a, T: OclPrm> From<&'a Image<T>> for KernelArg<'a, T> {
+    fn from(image: &'a Image<T>) -> KernelArg<'a, T> {
+        KernelArg::Mem(&image.obj_core)
+    }
+}
+
+impl<'a, T: OclPrm> KernelArgValue for &'a Image<T> {
+    fn set_kernel_arg(&self, kernel: &mut Kernel, arg_idx: u32) -> OclResult<()> {
+        // Type is ignored:
+        kernel.set_arg::<u8>(arg_idx, KernelArg::Mem(&self.obj_core))
+    }
+}
+
+impl<'a, T: OclPrm> KernelArgValue for &'a mut Image<T> {
+    fn set_kernel_arg(&self, kernel: &mut Kernel, arg_idx: u32) -> OclResult<()> {
+        // Type is ignored:
+        kernel.set_arg::<u8>(arg_idx, KernelArg::Mem(&self.obj_core))
+    }
+}
+
+
+/// A single layer of a 1d or 2d image array.
+///
+/// Create one with `Image::layer` or `Image::layers`. Commands created from
+/// a layer have their origin and region translated to cover only that layer:
+///
+/// * 1d image array: origin `[0, layer_idx, 0]`, region `[width, 1, 1]`.
+/// * 2d image array: origin `[0, 0, layer_idx]`, region `[width, height, 1]`.
+///
+#[derive(Clone, Debug)]
+pub struct ImageLayer<'a, E: 'a + OclPrm> {
+    image: &'a Image<E>,
+    layer_idx: usize,
+    origin: [usize; 3],
+    region: [usize; 3],
+}
+
+impl<'a, E: 'a + OclPrm> ImageLayer<'a, E> {
+    /// Returns a new handle to layer number `layer_idx` of `image`.
+    fn new(image: &'a Image<E>, layer_idx: usize) -> OclResult<ImageLayer<'a, E>> {
+        let dims = try!(image.dims.to_lens());
+
+        let (origin, region) = match image.image_type() {
+            MemObjectType::Image1dArray => ([0, layer_idx, 0], [dims[0], 1, 1]),
+            MemObjectType::Image2dArray => ([0, 0, layer_idx], [dims[0], dims[1], 1]),
+            image_type => return OclError::err(format!("ocl::ImageLayer::new(): Only \
+                'Image1dArray' and 'Image2dArray' images have layers (image type: {:?}).",
+                image_type)),
+        };
+
+        if layer_idx >= image.array_size() {
+            return OclError::err(format!("ocl::ImageLayer::new(): Layer index ({}) out of \
+                range. Image array size: {}.", layer_idx, image.array_size()));
+        }
+
+        Ok(ImageLayer {
+            image: image,
+            layer_idx: layer_idx,
+            origin: origin,
+            region: region,
+        })
+    }
+
+    /// Returns an image command builder with its origin and region set to
+    /// cover this layer.
+    ///
+    /// Run `.enq()` to enqueue the command.
+    ///
+    pub fn cmd<'b>(&'b self) -> ImageCmd<'b, E> {
+        self.image.cmd().origin(self.origin).region(self.region)
+    }
+
+    /// Returns an image command builder set to read this layer.
+    ///
+    /// Run `.enq()` to enqueue the command.
+    ///
+    pub fn read<'b>(&'b self, data: &'b mut [E]) -> ImageCmd<'b, E> {
+        self.cmd().read(data)
+    }
+
+    /// Returns an image command builder set to write this layer.
+    ///
+    /// Run `.enq()` to enqueue the command.
+    ///
+    pub fn write<'b>(&'b self, data: &'b [E]) -> ImageCmd<'b, E> {
+        self.cmd().write(data)
+    }
+
+    /// Returns an image command builder set to read this layer, converting
+    /// pixels into four component (RGBA) floats.
+    ///
+    /// Run `.enq()` to enqueue the command.
+    ///
+    pub fn read_f32<'b>(&'b self, data: &'b mut [f32]) -> ImageCmd<'b, E> {
+        self.cmd().read_f32(data)
+    }
+
+    /// Returns an image command builder set to write this layer, converting
+    /// four component (RGBA) floats into the image's format.
+    ///
+    /// Run `.enq()` to enqueue the command.
+    ///
+    pub fn write_f32<'b>(&'b self, data: &'b [f32]) -> ImageCmd<'b, E> {
+        self.cmd().write_f32(data)
+    }
+
+    /// Returns an image command builder set to fill this layer.
+    ///
+    /// See `ImageCmd::fill` for details about `color`.
+    ///
+    /// Run `.enq()` to enqueue the command.
+    ///
+    pub fn fill<'b>(&'b self, color: E) -> ImageCmd<'b, E> {
+        self.cmd().fill(color)
+    }
+
+    /// Returns an image command builder set to copy this layer to the layer,
+    /// `dst_layer`, of another (or the same) image array.
+    ///
+    /// Run `.enq()` to enqueue the command.
+    ///
+    pub fn copy<'b>(&'b self, dst_layer: &'b ImageLayer<E>) -> ImageCmd<'b, E> {
+        let mut cmd = self.cmd();
+        cmd.kind = ImageCmdKind::Copy {
+            dst_image: dst_layer.image.core_as_ref(),
+            dst_origin: dst_layer.origin,
+        };
+        cmd
+    }
+
+    /// Returns the image this layer belongs to.
+    pub fn image(&self) -> &'a Image<E> {
+        self.image
+    }
+
+    /// Returns the index of this layer within its image array.
+    pub fn idx(&self) -> usize {
+        self.layer_idx
+    }
+
+    /// Returns the origin of this layer within its image array.
+    pub fn origin(&self) -> [usize; 3] {
+        self.origin
+    }
+
+    /// Returns the region (size) of this layer.
+    pub fn region(&self) -> [usize; 3] {
+        self.region
+    }
+
+    /// Returns the total number of pixels in this layer.
+    pub fn pixel_count(&self) -> usize {
+        self.region[0] * self.region[1] * self.region[2]
+    }
+
+    /// Returns the total number of pixel elements in this layer.
+    pub fn element_count(&self) -> usize {
+        self.pixel_count() * self.image.pixel_element_len()
+    }
+}
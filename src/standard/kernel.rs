@@ -3,14 +3,39 @@
 use std;
 use std::convert::Into;
 use std::collections::HashMap;
+use std::sync::Mutex;
 use core::{self, OclPrm, Kernel as KernelCore, CommandQueue as CommandQueueCore, Mem as MemCore,
-    KernelArg, KernelInfo, KernelInfoResult, KernelArgInfo, KernelArgInfoResult,
-    KernelWorkGroupInfo, KernelWorkGroupInfoResult, ClEventPtrNew, ClWaitList};
+    Sampler as SamplerCore, KernelArg, KernelInfo, KernelInfoResult, KernelArgInfo, KernelArgInfoResult,
+    KernelWorkGroupInfo, KernelWorkGroupInfoResult, MemInfo, MemInfoResult, MemObjectType,
+    DeviceInfo, DeviceInfoResult,
+    ClEventPtrNew, ClWaitList};
 use core::error::{Result as OclResult, Error as OclError};
-use standard::{SpatialDims, Program, Queue, WorkDims, Sampler, Device, AsMemRef};
+use core::{ClChar2, ClChar3, ClChar4, ClChar8, ClChar16, ClUchar2, ClUchar3, ClUchar4, ClUchar8,
+    ClUchar16, ClShort2, ClShort3, ClShort4, ClShort8, ClShort16, ClUshort2, ClUshort3, ClUshort4,
+    ClUshort8, ClUshort16, ClInt2, ClInt3, ClInt4, ClInt8, ClInt16, ClUint2, ClUint3, ClUint4,
+    ClUint8, ClUint16, ClLong1, ClLong2, ClLong3, ClLong4, ClLong8, ClLong16, ClUlong1, ClUlong2,
+    ClUlong3, ClUlong4, ClUlong8, ClUlong16, ClFloat2, ClFloat3, ClFloat4, ClFloat8, ClFloat16,
+    ClDouble2, ClDouble3, ClDouble4, ClDouble8, ClDouble16};
+use standard::{SpatialDims, Program, Queue, WorkDims, Sampler, Device, AsMemRef, KernelArgValue,
+    Event, ResourceTracker, Resource};
+use standard::arg_type::{self, ArgType};
+use standard::work_group::{self, WorkGroupLimits};
 
 const PRINT_DEBUG: bool = false;
 
+#[cfg(feature = "opencl_version_2_1")]
+mod ffi_2_1 {
+    //! OpenCL 2.1 kernel functions not yet available in `cl-sys`.
+
+    use ffi::{cl_kernel, cl_int};
+
+    #[cfg_attr(target_os = "macos", link(name = "OpenCL", kind = "framework"))]
+    #[cfg_attr(not(target_os = "macos"), link(name = "OpenCL"))]
+    extern "system" {
+        pub fn clCloneKernel(source_kernel: cl_kernel, errcode_ret: *mut cl_int) -> cl_kernel;
+    }
+}
+
 /// A kernel command builder used to queue a kernel with a mix of default
 /// and optionally specified arguments.
 pub struct KernelCmd<'k> {
@@ -19,6 +44,12 @@ pub struct KernelCmd<'k> {
     gwo: SpatialDims,
     gws: SpatialDims,
     lws: SpatialDims,
+    auto_lws: bool,
+    real_gws_arg: Option<u32>,
+    wg_limits: Option<&'k WorkGroupLimits>,
+    arg_vals: &'k [Option<ArgVal>],
+    max_chunk_items: Option<usize>,
+    tracker: &'k ResourceTracker,
     wait_list: Option<&'k ClWaitList>,
     dest_list: Option<&'k mut ClEventPtrNew>,
 }
@@ -26,8 +57,14 @@ pub struct KernelCmd<'k> {
 /// [UNSTABLE]: All methods still being tuned.
 impl<'k> KernelCmd<'k> {
     /// Specifies a queue to use for this call only.
-    pub fn queue<Q: AsRef<CommandQueueCore>>(mut self, queue: &'k Q) -> KernelCmd<'k> {
-        self.queue = queue.as_ref();
+    ///
+    /// The objects used by the command are retained by that queue's
+    /// `ResourceTracker`.
+    pub fn queue(mut self, queue: &'k Queue) -> KernelCmd<'k> {
+        self.queue = queue.core_as_ref();
+        self.tracker = queue.tracker();
+        // Limits are cached only for the kernel's default queue:
+        self.wg_limits = None;
         self
     }
 
@@ -46,6 +83,43 @@ impl<'k> KernelCmd<'k> {
     /// Specifies a local work size for this call only.
     pub fn lws<D: Into<SpatialDims>>(mut self, lws: D) -> KernelCmd<'k> {
         self.lws = lws.into();
+        self.auto_lws = false;
+        self
+    }
+
+    /// Chooses a local work size for this call only, based on the kernel's
+    /// work group size limits on the queue's device (see
+    /// `Kernel::auto_lws`).
+    pub fn auto_lws(mut self) -> KernelCmd<'k> {
+        self.auto_lws = true;
+        self
+    }
+
+    /// Pads the global work size up to a multiple of the local work size
+    /// for this call only, passing the real global work size to the kernel
+    /// as the argument at `real_gws_arg_idx` (see `Kernel::pad_gws`).
+    pub fn pad_gws(mut self, real_gws_arg_idx: u32) -> KernelCmd<'k> {
+        self.real_gws_arg = Some(real_gws_arg_idx);
+        self
+    }
+
+    /// Splits the launch into several enqueues of at most `max_work_items`
+    /// work items each.
+    ///
+    /// Useful when a driver rejects or times out on a very large global work
+    /// size. The global range is divided along the slowest varying (last)
+    /// dimension into chunks whose lengths are multiples of the local work
+    /// size in that dimension (a chunk may therefore exceed
+    /// `max_work_items` if a single row of work groups does). Each chunk is
+    /// offset using the global work offset so that `get_global_id` returns
+    /// the same values as it would for a single launch (`get_global_size`,
+    /// however, returns the chunk size).
+    ///
+    /// Each chunk waits on the previous one. The first waits on the wait
+    /// list (`::ewait`) and the event for the last (`::enew`) completes once
+    /// the whole range has.
+    pub fn chunked(mut self, max_work_items: usize) -> KernelCmd<'k> {
+        self.max_chunk_items = Some(max_work_items);
         self
     }
 
@@ -76,26 +150,673 @@ impl<'k> KernelCmd<'k> {
     }
 
     /// Enqueues this kernel command.
+    ///
+    /// ## Errors
+    ///
+    /// Before enqueuing, returns an error naming the kernel if:
+    ///
+    /// - the global work size is unspecified or the dimension counts of the
+    ///   global work size, local work size, and global work offset differ,
+    /// - any argument has not been set,
+    /// - the local work size does not evenly divide the global work size,
+    ///   exceeds the device's `MaxWorkItemSizes`, `MaxWorkGroupSize`, or
+    ///   `MaxWorkItemDimensions`, the kernel's `WorkGroupSize`, or does not
+    ///   match the kernel's `CompileWorkGroupSize` (`reqd_work_group_size`).
+    ///
     pub fn enq(self) -> OclResult<()> {
         let dim_count = self.gws.dim_count();
 
-        let gws = match self.gws.to_work_size() {
+        let mut gws = match self.gws.to_work_size() {
             Some(gws) => gws,
-            None => return OclError::err("ocl::KernelCmd::enqueue: Global Work Size ('gws') \
-                cannot be left unspecified. Set a default for the kernel or pass a valid parameter."),
+            None => return self.err("Global Work Size ('gws') cannot be left unspecified. \
+                Set a default for the kernel or pass a valid parameter."),
+        };
+
+        if self.lws.dim_count() != 0 && self.lws.dim_count() != dim_count {
+            return self.err(format!("The local work size ('lws': {:?}) and global work size \
+                ('gws': {:?}) have differing dimension counts.", self.lws, self.gws));
+        }
+
+        if self.gwo.dim_count() != 0 && self.gwo.dim_count() != dim_count {
+            return self.err(format!("The global work offset ('gwo': {:?}) and global work \
+                size ('gws': {:?}) have differing dimension counts.", self.gwo, self.gws));
+        }
+
+        let unset_args: Vec<_> = self.arg_vals.iter().enumerate()
+            .filter(|&(arg_idx, arg_val)| {
+                arg_val.is_none() && self.real_gws_arg != Some(arg_idx as u32)
+            })
+            .map(|(arg_idx, _)| arg_idx)
+            .collect();
+
+        if !unset_args.is_empty() {
+            return self.err(format!("Arguments at the following indexes have not been set: \
+                {:?}.", unset_args));
+        }
+
+        let queried_limits;
+        let limits = match self.wg_limits {
+            Some(limits) => limits,
+            None => {
+                queried_limits = try!(WorkGroupLimits::query(self.kernel, self.queue));
+                &queried_limits
+            },
         };
 
+        let mut lws = self.lws.to_work_size();
+
+        // Padding requires a local work size to pad to:
+        if self.auto_lws || (self.real_gws_arg.is_some() && lws.is_none()) {
+            lws = Some(limits.auto_lws(&gws, dim_count, self.real_gws_arg.is_some()));
+        }
+
+        if let (Some(arg_idx), Some(lws)) = (self.real_gws_arg, lws) {
+            try!(work_group::set_real_gws_arg(self.kernel, arg_idx, &gws, dim_count));
+            gws = work_group::pad_gws(&gws, &lws, dim_count);
+        }
+
+        if let Err(err) = limits.validate(&gws, lws.as_ref(), dim_count) {
+            return self.err(err.to_string());
+        }
+
         if PRINT_DEBUG {
             println!("Enqueuing kernel: '{}'...",
                 core::get_kernel_info(self.kernel, KernelInfo::FunctionName));
         }
 
-        core::enqueue_kernel(self.queue, self.kernel, dim_count, self.gwo.to_work_offset(),
-            &gws, self.lws.to_work_size(), self.wait_list, self.dest_list)
+        let gwo = self.gwo.to_work_offset();
+
+        let chunks = match self.max_chunk_items {
+            Some(max_work_items) => {
+                work_group::chunk_ranges(&gws, lws.as_ref(), dim_count, max_work_items)
+            },
+            None => Vec::new(),
+        };
+
+        // Retain the kernel and its memory object and sampler arguments until
+        // the command completes:
+        let mut resources = vec![Resource::Kernel(self.kernel.clone())];
+        resources.extend(self.arg_vals.iter().filter_map(|arg_val| {
+            match *arg_val {
+                Some(ArgVal::Mem(ref mem, _)) => Some(Resource::Mem(mem.clone())),
+                Some(ArgVal::Sampler(ref smp)) => Some(Resource::Sampler(smp.clone())),
+                _ => None,
+            }
+        }));
+
+        let (queue, kernel, wait_list) = (self.queue, self.kernel, self.wait_list);
+
+        self.tracker.enqueue(resources, self.dest_list, |dest_list| {
+            enqueue_chunks(queue, kernel, dim_count, gwo, &gws, lws, &chunks, wait_list,
+                dest_list)
+        })
+    }
+
+    /// Returns an error prefixed with the kernel name.
+    fn err<S: Into<String>>(&self, msg: S) -> OclResult<()> {
+        OclError::err(format!("ocl::KernelCmd::enq(): Unable to enqueue kernel '{}': {}",
+            core::get_kernel_info(self.kernel, KernelInfo::FunctionName), msg.into()))
+    }
+}
+
+
+
+
+/// Enqueues `kernel` once for each chunk in `chunks` (along the last
+/// dimension), each waiting on the previous, or once for the whole range if
+/// there is at most one chunk.
+fn enqueue_chunks(queue: &CommandQueueCore, kernel: &KernelCore, dim_count: u32,
+        gwo: Option<[usize; 3]>, gws: &[usize; 3], lws: Option<[usize; 3]>,
+        chunks: &[(usize, usize)], wait_list: Option<&ClWaitList>,
+        dest_list: Option<&mut ClEventPtrNew>) -> OclResult<()>
+{
+    if chunks.len() <= 1 {
+        return core::enqueue_kernel(queue, kernel, dim_count, gwo, gws, lws, wait_list,
+            dest_list);
+    }
+
+    let last_dim = dim_count as usize - 1;
+    let chunk_work = |&(start, len): &(usize, usize)| {
+        let mut chunk_gwo = gwo.unwrap_or([0, 0, 0]);
+        let mut chunk_gws = *gws;
+        chunk_gwo[last_dim] += start;
+        chunk_gws[last_dim] = len;
+        (chunk_gwo, chunk_gws)
+    };
+
+    // Chain every chunk but the last, each waiting on the previous:
+    let mut prev_event: Option<Event> = None;
+
+    for chunk in chunks[..chunks.len() - 1].iter() {
+        let (chunk_gwo, chunk_gws) = chunk_work(chunk);
+        let mut event = Event::empty();
+
+        {
+            let wait_list = match prev_event {
+                Some(ref prev_event) => Some(prev_event as &ClWaitList),
+                None => wait_list,
+            };
+
+            try!(core::enqueue_kernel(queue, kernel, dim_count, Some(chunk_gwo), &chunk_gws,
+                lws, wait_list, Some(&mut event as &mut ClEventPtrNew)));
+        }
+
+        prev_event = Some(event);
+    }
+
+    let (chunk_gwo, chunk_gws) = chunk_work(&chunks[chunks.len() - 1]);
+
+    core::enqueue_kernel(queue, kernel, dim_count, Some(chunk_gwo), &chunk_gws, lws,
+        prev_event.as_ref().map(|ev| ev as &ClWaitList), dest_list)
+}
+
+
+/// A function which sets a single kernel argument at the given index.
+type ArgSetter<'b> = Box<Fn(&mut Kernel, u32) -> OclResult<()> + 'b>;
+
+/// A builder for `Kernel`.
+///
+/// Unlike the builder-style argument methods on `Kernel` itself (`::arg_buf`,
+/// etc.), which panic if an argument is invalid, nothing is created or set
+/// until `::build` is called, which returns an error if the kernel cannot be
+/// created or if any argument cannot be set.
+///
+/// ## Examples
+///
+/// ```text
+/// let kernel = Kernel::builder()
+///     .name("add")
+///     .program(&program)
+///     .queue(queue.clone())
+///     .gws([1024])
+///     .arg_buf(&buffer)
+///     .arg_scl_named::<f32>("addend", Some(10.0))
+///     .build()?;
+/// ```
+///
+pub struct KernelBuilder<'b> {
+    name: Option<String>,
+    program: Option<&'b Program>,
+    queue: Option<Queue>,
+    gwo: SpatialDims,
+    gws: SpatialDims,
+    lws: SpatialDims,
+    auto_lws: bool,
+    real_gws_arg: Option<u32>,
+    args: Vec<(Option<&'static str>, ArgSetter<'b>)>,
+}
+
+impl<'b> KernelBuilder<'b> {
+    /// Returns a new, empty, `KernelBuilder`.
+    ///
+    /// A name, program, and queue must be specified before building.
+    pub fn new() -> KernelBuilder<'b> {
+        KernelBuilder {
+            name: None,
+            program: None,
+            queue: None,
+            gwo: SpatialDims::Unspecified,
+            gws: SpatialDims::Unspecified,
+            lws: SpatialDims::Unspecified,
+            auto_lws: false,
+            real_gws_arg: None,
+            args: Vec::with_capacity(16),
+        }
+    }
+
+    /// Specifies the function name of the kernel.
+    pub fn name<S: Into<String>>(&mut self, name: S) -> &mut KernelBuilder<'b> {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Specifies the program containing the kernel.
+    pub fn program(&mut self, program: &'b Program) -> &mut KernelBuilder<'b> {
+        self.program = Some(program);
+        self
+    }
+
+    /// Specifies the default queue.
+    pub fn queue(&mut self, queue: Queue) -> &mut KernelBuilder<'b> {
+        self.queue = Some(queue);
+        self
+    }
+
+    /// Specifies the default global work offset.
+    pub fn gwo<D: Into<SpatialDims>>(&mut self, gwo: D) -> &mut KernelBuilder<'b> {
+        self.gwo = gwo.into();
+        self
+    }
+
+    /// Specifies the default global work size.
+    pub fn gws<D: Into<SpatialDims>>(&mut self, gws: D) -> &mut KernelBuilder<'b> {
+        self.gws = gws.into();
+        self
+    }
+
+    /// Specifies the default local work size.
+    pub fn lws<D: Into<SpatialDims>>(&mut self, lws: D) -> &mut KernelBuilder<'b> {
+        self.lws = lws.into();
+        self.auto_lws = false;
+        self
+    }
+
+    /// Specifies that the local work size is to be chosen automatically
+    /// (see `Kernel::auto_lws`).
+    pub fn auto_lws(&mut self) -> &mut KernelBuilder<'b> {
+        self.auto_lws = true;
+        self
+    }
+
+    /// Specifies that the global work size is to be padded, with the real
+    /// size passed as the argument at `real_gws_arg_idx` (see
+    /// `Kernel::pad_gws`).
+    pub fn pad_gws(&mut self, real_gws_arg_idx: u32) -> &mut KernelBuilder<'b> {
+        self.real_gws_arg = Some(real_gws_arg_idx);
+        self
+    }
+
+    /// Adds a new argument specifying the buffer object represented by
+    /// `buffer`. Argument is added to the bottom of the argument order.
+    pub fn arg_buf<T, M>(&mut self, buffer: M) -> &mut KernelBuilder<'b>
+            where T: 'b + OclPrm, M: 'b + AsMemRef<T>
+    {
+        self.arg_buf_named_opt::<T, M>(None, Some(buffer))
+    }
+
+    /// Adds a new argument specifying the image object represented by
+    /// `image`. Argument is added to the bottom of the argument order.
+    pub fn arg_img<T, M>(&mut self, image: M) -> &mut KernelBuilder<'b>
+            where T: 'b + OclPrm, M: 'b + AsMemRef<T>
+    {
+        self.arg_img_named_opt::<T, M>(None, Some(image))
+    }
+
+    /// Adds a new argument specifying the sampler object represented by
+    /// `sampler`. Argument is added to the bottom of the argument order.
+    pub fn arg_smp(&mut self, sampler: &'b Sampler) -> &mut KernelBuilder<'b> {
+        self.arg_smp_named_opt(None, Some(sampler))
+    }
+
+    /// Adds a new argument specifying the value: `scalar`. Argument is added
+    /// to the bottom of the argument order.
+    pub fn arg_scl<T: 'b + OclPrm>(&mut self, scalar: T) -> &mut KernelBuilder<'b> {
+        self.push_arg(None, move |k, idx| k.set_arg::<T>(idx, KernelArg::Scalar(scalar)))
+    }
+
+    /// Adds a new argument specifying the value: `vector`. Argument is added
+    /// to the bottom of the argument order.
+    pub fn arg_vec<T: 'b + OclPrm>(&mut self, vector: T) -> &mut KernelBuilder<'b> {
+        self.push_arg(None, move |k, idx| k.set_arg::<T>(idx, KernelArg::Vector(vector)))
+    }
+
+    /// Adds a new argument specifying the allocation of a local variable of
+    /// size `length * sizeof(T)` bytes.
+    pub fn arg_loc<T: 'b + OclPrm>(&mut self, length: usize) -> &mut KernelBuilder<'b> {
+        self.push_arg(None, move |k, idx| k.set_arg::<T>(idx, KernelArg::Local(&length)))
+    }
+
+    /// Adds a new named argument specifying the allocation of a local
+    /// variable of size `length * sizeof(T)` bytes.
+    pub fn arg_loc_named<T: 'b + OclPrm>(&mut self, name: &'static str, length: usize)
+            -> &mut KernelBuilder<'b>
+    {
+        self.push_arg(Some(name), move |k, idx| k.set_arg::<T>(idx, KernelArg::Local(&length)))
+    }
+
+    /// Adds a new named argument specifying the value: `scalar` (or the
+    /// default value if `None`).
+    pub fn arg_scl_named<T: 'b + OclPrm>(&mut self, name: &'static str, scalar_opt: Option<T>)
+            -> &mut KernelBuilder<'b>
+    {
+        let scalar = scalar_opt.unwrap_or_default();
+        self.push_arg(Some(name), move |k, idx| k.set_arg::<T>(idx, KernelArg::Scalar(scalar)))
+    }
+
+    /// Adds a new named argument specifying the value: `vector` (or the
+    /// default value if `None`).
+    pub fn arg_vec_named<T: 'b + OclPrm>(&mut self, name: &'static str, vector_opt: Option<T>)
+            -> &mut KernelBuilder<'b>
+    {
+        let vector = vector_opt.unwrap_or_default();
+        self.push_arg(Some(name), move |k, idx| k.set_arg::<T>(idx, KernelArg::Vector(vector)))
+    }
+
+    /// Adds a new named argument specifying the buffer object represented by
+    /// `buffer` (or null if `None`).
+    pub fn arg_buf_named<T, M>(&mut self, name: &'static str, buffer_opt: Option<M>)
+            -> &mut KernelBuilder<'b>
+            where T: 'b + OclPrm, M: 'b + AsMemRef<T>
+    {
+        self.arg_buf_named_opt::<T, M>(Some(name), buffer_opt)
+    }
+
+    /// Adds a new named argument specifying the image object represented by
+    /// `image` (or null if `None`).
+    pub fn arg_img_named<T, M>(&mut self, name: &'static str, image_opt: Option<M>)
+            -> &mut KernelBuilder<'b>
+            where T: 'b + OclPrm, M: 'b + AsMemRef<T>
+    {
+        self.arg_img_named_opt::<T, M>(Some(name), image_opt)
+    }
+
+    /// Adds a new named argument specifying the sampler object represented by
+    /// `sampler` (or null if `None`).
+    pub fn arg_smp_named(&mut self, name: &'static str, sampler_opt: Option<&'b Sampler>)
+            -> &mut KernelBuilder<'b>
+    {
+        self.arg_smp_named_opt(Some(name), sampler_opt)
+    }
+
+    /// Creates the kernel and sets each argument in order.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if a name, program, or queue has not been specified,
+    /// if the kernel cannot be created, or if any argument cannot be set
+    /// (see `Kernel::set_arg`).
+    ///
+    pub fn build(&self) -> OclResult<Kernel> {
+        let name = match self.name {
+            Some(ref name) => name.clone(),
+            None => return OclError::err("ocl::KernelBuilder::build(): No kernel name specified. \
+                Use '::name'."),
+        };
+        let program = match self.program {
+            Some(program) => program,
+            None => return OclError::err(format!("ocl::KernelBuilder::build(): No program \
+                specified for kernel '{}'. Use '::program'.", name)),
+        };
+        let queue = match self.queue {
+            Some(ref queue) => queue.clone(),
+            None => return OclError::err(format!("ocl::KernelBuilder::build(): No queue \
+                specified for kernel '{}'. Use '::queue'.", name)),
+        };
+
+        let mut kernel = try!(Kernel::new(name, program, queue))
+            .gwo(self.gwo)
+            .gws(self.gws)
+            .lws(self.lws);
+        kernel.auto_lws = self.auto_lws;
+        kernel.real_gws_arg = self.real_gws_arg;
+
+        for &(name_opt, ref setter) in self.args.iter() {
+            let arg_idx = try!(kernel.push_arg(|k, idx| setter(k, idx)));
+
+            if let Some(name) = name_opt {
+                kernel.named_args.insert(name, arg_idx);
+            }
+        }
+
+        Ok(kernel)
+    }
+
+    fn push_arg<F>(&mut self, name: Option<&'static str>, setter: F) -> &mut KernelBuilder<'b>
+            where F: 'b + Fn(&mut Kernel, u32) -> OclResult<()>
+    {
+        self.args.push((name, Box::new(setter)));
+        self
+    }
+
+    fn arg_buf_named_opt<T, M>(&mut self, name: Option<&'static str>, buffer_opt: Option<M>)
+            -> &mut KernelBuilder<'b>
+            where T: 'b + OclPrm, M: 'b + AsMemRef<T>
+    {
+        self.push_arg(name, move |k, idx| match buffer_opt {
+            Some(ref buffer) => k.set_arg::<T>(idx, KernelArg::Mem(buffer.as_mem_ref())),
+            None => k.set_arg::<T>(idx, KernelArg::MemNull),
+        })
     }
+
+    fn arg_img_named_opt<T, M>(&mut self, name: Option<&'static str>, image_opt: Option<M>)
+            -> &mut KernelBuilder<'b>
+            where T: 'b + OclPrm, M: 'b + AsMemRef<T>
+    {
+        // Type is ignored:
+        self.push_arg(name, move |k, idx| match image_opt {
+            Some(ref image) => k.set_arg::<u8>(idx, KernelArg::Mem(image.as_mem_ref())),
+            None => k.set_arg::<u8>(idx, KernelArg::MemNull),
+        })
+    }
+
+    fn arg_smp_named_opt(&mut self, name: Option<&'static str>, sampler_opt: Option<&'b Sampler>)
+            -> &mut KernelBuilder<'b>
+    {
+        // Type is ignored:
+        self.push_arg(name, move |k, idx| match sampler_opt {
+            Some(sampler) => k.set_arg::<u8>(idx, KernelArg::Sampler(sampler)),
+            None => k.set_arg::<u8>(idx, KernelArg::SamplerNull),
+        })
+    }
+}
+
+
+/// Returns a copy of `kernel`'s core, arguments included, made with
+/// `clCloneKernel` or `None` if its device does not support OpenCL 2.1.
+#[cfg(feature = "opencl_version_2_1")]
+fn clone_kernel_core(kernel: &Kernel) -> OclResult<Option<KernelCore>> {
+    use core::OpenclVersion;
+    use ffi::{self, cl_kernel};
+
+    if kernel.queue.device_version() < OpenclVersion::new(2, 1) {
+        return Ok(None);
+    }
+
+    let mut errcode: ffi::cl_int = 0;
+
+    let kernel_ptr: cl_kernel = unsafe {
+        ffi_2_1::clCloneKernel(kernel.obj_core.as_ptr(), &mut errcode)
+    };
+
+    if errcode != ffi::CL_SUCCESS || kernel_ptr.is_null() {
+        return OclError::err(format!("ocl::Kernel::duplicate(): 'clCloneKernel' failed \
+            with error code: {}.", errcode));
+    }
+
+    unsafe { Ok(Some(KernelCore::from_fresh_ptr(kernel_ptr))) }
+}
+
+#[cfg(not(feature = "opencl_version_2_1"))]
+fn clone_kernel_core(_: &Kernel) -> OclResult<Option<KernelCore>> {
+    Ok(None)
+}
+
+
+/// The kind of value set for a kernel argument.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KernelArgKind {
+    /// A buffer or sub-buffer.
+    Buffer,
+    /// An image.
+    Image,
+    /// A null memory object.
+    MemNull,
+    /// A sampler.
+    Sampler,
+    /// A null sampler.
+    SamplerNull,
+    /// A scalar value.
+    Scalar,
+    /// A vector value.
+    Vector,
+    /// A local memory allocation.
+    Local,
+    /// No value has been set.
+    Unset,
+}
+
+
+/// A description of the value set for a kernel argument, returned by
+/// `Kernel::args`.
+#[derive(Clone, Debug)]
+pub struct KernelArgDesc {
+    /// The argument index.
+    pub index: u32,
+    /// The kind of value.
+    pub kind: KernelArgKind,
+    /// The Rust type of the value (or of the elements of a buffer or local
+    /// allocation) if it is a built-in scalar or vector type. Image
+    /// element types are not tracked.
+    pub type_name: Option<&'static str>,
+    /// The length of a buffer or local allocation in elements.
+    pub mem_len: Option<usize>,
+    /// The value of a scalar or vector, formatted using `Debug`.
+    pub value: Option<String>,
 }
 
 
+/// The element type of a kernel argument value.
+#[derive(Clone, Copy)]
+struct ElemType {
+    name: Option<&'static str>,
+    size: usize,
+    fmt_value: fn(&[u8]) -> String,
+}
+
+impl ElemType {
+    fn of<T: OclPrm>() -> ElemType {
+        fn fmt_value<T: OclPrm>(bytes: &[u8]) -> String {
+            let mut val = T::default();
+            assert_eq!(bytes.len(), std::mem::size_of::<T>());
+            unsafe {
+                std::ptr::copy_nonoverlapping(bytes.as_ptr(), &mut val as *mut T as *mut u8,
+                    bytes.len());
+            }
+            format!("{:?}", val)
+        }
+
+        ElemType {
+            name: arg_type::rust_type_name::<T>(),
+            size: std::mem::size_of::<T>(),
+            fmt_value: fmt_value::<T>,
+        }
+    }
+}
+
+impl std::fmt::Debug for ElemType {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("ElemType")
+            .field("name", &self.name)
+            .field("size", &self.size)
+            .finish()
+    }
+}
+
+
+/// A copy of the value of a kernel argument.
+///
+/// Memory objects and samplers are retained so that they outlive every
+/// reference the kernel holds to them. All values are kept so that they can
+/// be set again on a duplicate kernel (see `Kernel::duplicate`) and
+/// described (see `Kernel::args`).
+#[derive(Clone, Debug)]
+enum ArgVal {
+    Mem(MemCore, ElemType),
+    MemNull(ElemType),
+    Sampler(SamplerCore),
+    SamplerNull,
+    Scalar(Vec<u8>, ElemType),
+    Vector(Vec<u8>, ElemType),
+    /// The size of the local allocation in bytes.
+    Local(usize, ElemType),
+}
+
+impl ArgVal {
+    /// Copies the value of `arg`.
+    fn new<T: OclPrm>(arg: &KernelArg<T>) -> ArgVal {
+        fn bytes_of<T: OclPrm>(val: &T) -> Vec<u8> {
+            unsafe {
+                std::slice::from_raw_parts(val as *const T as *const u8, std::mem::size_of::<T>())
+            }.to_vec()
+        }
+
+        let elem_type = ElemType::of::<T>();
+
+        match *arg {
+            KernelArg::Mem(mem) => ArgVal::Mem(mem.clone(), elem_type),
+            KernelArg::MemNull => ArgVal::MemNull(elem_type),
+            KernelArg::Sampler(smp) => ArgVal::Sampler(smp.clone()),
+            KernelArg::SamplerNull => ArgVal::SamplerNull,
+            KernelArg::Scalar(ref val) => ArgVal::Scalar(bytes_of(val), elem_type),
+            KernelArg::Vector(ref val) => ArgVal::Vector(bytes_of(val), elem_type),
+            KernelArg::Local(len) => ArgVal::Local(*len * elem_type.size, elem_type),
+        }
+    }
+
+    /// Sets this value as the argument at `arg_idx` of `kernel`.
+    fn set(&self, kernel: &KernelCore, arg_idx: u32) -> OclResult<()> {
+        // Element types are irrelevant for everything but values, whose
+        // sizes are known only in bytes (`u8` keeps local sizes in bytes):
+        match *self {
+            ArgVal::Mem(ref mem, _) => {
+                core::set_kernel_arg::<u8>(kernel, arg_idx, KernelArg::Mem(mem))
+            },
+            ArgVal::MemNull(_) => core::set_kernel_arg::<u8>(kernel, arg_idx, KernelArg::MemNull),
+            ArgVal::Sampler(ref smp) => {
+                core::set_kernel_arg::<u8>(kernel, arg_idx, KernelArg::Sampler(smp))
+            },
+            ArgVal::SamplerNull => {
+                core::set_kernel_arg::<u8>(kernel, arg_idx, KernelArg::SamplerNull)
+            },
+            ArgVal::Local(ref size, _) => {
+                core::set_kernel_arg::<u8>(kernel, arg_idx, KernelArg::Local(size))
+            },
+            ArgVal::Scalar(ref bytes, _) | ArgVal::Vector(ref bytes, _) => {
+                use ffi;
+
+                let errcode = unsafe { ffi::clSetKernelArg(kernel.as_ptr(), arg_idx,
+                    bytes.len(), bytes.as_ptr() as *const ffi::c_void) };
+
+                if errcode != ffi::CL_SUCCESS {
+                    return OclError::err(format!("ocl::Kernel: 'clSetKernelArg' failed for \
+                        argument {} with error code: {}.", arg_idx, errcode));
+                }
+                Ok(())
+            },
+        }
+    }
+
+    /// Returns a description of this value.
+    ///
+    /// Memory objects are queried for their type and size. Image element
+    /// types are not tracked (images are always set using `u8`).
+    fn desc(&self, index: u32) -> KernelArgDesc {
+        let (kind, elem_type, mem_len, value) = match *self {
+            ArgVal::Mem(ref mem, elem_type) => {
+                match arg_type::mem_object_type(mem) {
+                    Ok(MemObjectType::Buffer) => {
+                        let mem_len = match core::get_mem_object_info(mem, MemInfo::Size) {
+                            MemInfoResult::Size(size) => Some(size / elem_type.size),
+                            _ => None,
+                        };
+                        (KernelArgKind::Buffer, Some(elem_type), mem_len, None)
+                    },
+                    _ => (KernelArgKind::Image, None, None, None),
+                }
+            },
+            ArgVal::MemNull(elem_type) => (KernelArgKind::MemNull, Some(elem_type), None, None),
+            ArgVal::Sampler(_) => (KernelArgKind::Sampler, None, None, None),
+            ArgVal::SamplerNull => (KernelArgKind::SamplerNull, None, None, None),
+            ArgVal::Scalar(ref bytes, elem_type) => {
+                (KernelArgKind::Scalar, Some(elem_type), None, Some((elem_type.fmt_value)(bytes)))
+            },
+            ArgVal::Vector(ref bytes, elem_type) => {
+                (KernelArgKind::Vector, Some(elem_type), None, Some((elem_type.fmt_value)(bytes)))
+            },
+            ArgVal::Local(size, elem_type) => {
+                (KernelArgKind::Local, Some(elem_type), Some(size / elem_type.size), None)
+            },
+        };
+
+        KernelArgDesc {
+            index: index,
+            kind: kind,
+            type_name: elem_type.and_then(|et| et.name),
+            mem_len: mem_len,
+            value: value,
+        }
+    }
+}
 
 
 /// A kernel which represents a 'procedure'.
@@ -111,20 +832,33 @@ impl<'k> KernelCmd<'k> {
 /// `::core_as_ref.clone()` and use `ocl::core::enqueue_kernel(...)` to
 /// enqueue.
 ///
+/// Setting arguments is not thread-safe. To use the same kernel from several
+/// threads, give each its own copy using `::duplicate` (or a `KernelPool`).
+///
+///
+/// ## Panics
+///
+/// The builder-style argument methods (`::arg_buf`, `::arg_scl`, etc.) panic
+/// if an argument cannot be set. Use `KernelBuilder` (`Kernel::builder`) to
+/// create a kernel and set its arguments without panicking.
+///
 ///
 /// TODO: Add more details, examples, etc.
-/// TODO: Add information about panics and errors.
-/// TODO: Finish arg info formatting.
 #[derive(Debug)]
 pub struct Kernel {
     obj_core: KernelCore,
     named_args: HashMap<&'static str, u32>,
-    mem_args: Vec<Option<MemCore>>,
+    arg_vals: Vec<Option<ArgVal>>,
+    arg_types: Option<Vec<ArgType>>,
     arg_count: u32,
+    num_args: u32,
     queue: Queue,
     gwo: SpatialDims,
     gws: SpatialDims,
     lws: SpatialDims,
+    auto_lws: bool,
+    real_gws_arg: Option<u32>,
+    wg_limits: Option<WorkGroupLimits>,
 }
 
 // ######### IMPLEMENT THIS #########
@@ -144,6 +878,14 @@ pub struct Kernel {
 // }
 
 impl Kernel {
+    /// Returns a new `KernelBuilder`.
+    ///
+    /// Use this to create a kernel and set its arguments without panicking
+    /// if an argument is invalid.
+    pub fn builder<'b>() -> KernelBuilder<'b> {
+        KernelBuilder::new()
+    }
+
     /// Returns a new kernel.
     // TODO: Implement proper error handling (return result etc.).
     pub fn new<S: Into<String>, >(name: S, program: &Program, queue: Queue,
@@ -152,15 +894,41 @@ impl Kernel {
         let name = name.into();
         let obj_core = try!(core::create_kernel(program, &name));
 
+        // The argument count is always available and is used to detect unset
+        // arguments before enqueuing:
+        let num_args = match core::get_kernel_info(&obj_core, KernelInfo::NumArgs) {
+            KernelInfoResult::NumArgs(num_args) => num_args,
+            KernelInfoResult::Error(err) => return Err(*err),
+            _ => return OclError::err("ocl::Kernel::new(): Unexpected 'KernelInfoResult' \
+                variant."),
+        };
+
+        // Argument types are only available if the program was built with
+        // '-cl-kernel-arg-info' and are otherwise not checked:
+        let arg_types = (0..num_args).map(|arg_idx| {
+            ArgType::from_kernel(&obj_core, arg_idx, queue.device_version())
+        }).collect::<OclResult<Vec<_>>>().ok();
+
+        // Make room for every argument:
+        let arg_vals = (0..num_args).map(|_| None).collect();
+
+        // Cached for the default queue and used to validate work sizes:
+        let wg_limits = WorkGroupLimits::query(&obj_core, queue.core_as_ref()).ok();
+
         Ok(Kernel {
             obj_core: obj_core,
             named_args: HashMap::with_capacity(5),
             arg_count: 0,
-            mem_args: Vec::with_capacity(16),
+            num_args: num_args,
+            arg_vals: arg_vals,
+            arg_types: arg_types,
             queue: queue,
             gwo: SpatialDims::Unspecified,
             gws: SpatialDims::Unspecified,
             lws: SpatialDims::Unspecified,
+            auto_lws: false,
+            real_gws_arg: None,
+            wg_limits: wg_limits,
         })
     }
 
@@ -188,6 +956,46 @@ impl Kernel {
     /// making a call to enqueue or building a queue command with `::cmd`.
     pub fn lws<D: Into<SpatialDims>>(mut self, lws: D) -> Kernel {
         self.lws = lws.into();
+        self.auto_lws = false;
+        self
+    }
+
+    /// Sets the default local work size to be chosen automatically when
+    /// enqueuing (builder-style).
+    ///
+    /// The kernel's work group size and preferred work group size multiple
+    /// on the queue's device are used to choose a valid local work size for
+    /// each dimension which evenly divides the global work size (or, if
+    /// `::pad_gws` has been used, which the global work size is padded to).
+    ///
+    /// Superseded if a local work size is specified while building a queue
+    /// command with `::cmd`.
+    pub fn auto_lws(mut self) -> Kernel {
+        self.auto_lws = true;
+        self
+    }
+
+    /// Pads the global work size up to a multiple of the local work size
+    /// when enqueuing, passing the real global work size to the kernel as
+    /// the argument at `real_gws_arg_idx` (builder-style).
+    ///
+    /// The argument must be declared as a `uint` for a one dimensional global
+    /// work size, a `uint2` for two, or a `uint3` or `uint4` for three. Its
+    /// value is set each time the kernel is enqueued and should be used to
+    /// skip the padding work items, for example:
+    ///
+    /// ```text
+    /// __kernel void add(__global float* buffer, float addend, uint len) {
+    ///     if (get_global_id(0) < len) {
+    ///         buffer[get_global_id(0)] += addend;
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// If no local work size has been specified, one is chosen automatically
+    /// (see `::auto_lws`).
+    pub fn pad_gws(mut self, real_gws_arg_idx: u32) -> Kernel {
+        self.real_gws_arg = Some(real_gws_arg_idx);
         self
     }
 
@@ -238,12 +1046,22 @@ impl Kernel {
     /// `length * sizeof(T)` bytes (builder_style).
     ///
     /// Local variables are used to share data between work items in the same
-    /// workgroup.
+    /// workgroup. Use `::set_arg_loc` to change the size later.
     pub fn arg_loc<T: OclPrm>(mut self, length: usize) -> Kernel {
         self.new_arg_loc::<T>(length);
         self
     }
 
+    /// Adds a new named argument (in order) specifying the allocation of a
+    /// local variable of size `length * sizeof(T)` bytes (builder-style).
+    ///
+    /// Named arguments can be easily modified later using `::set_arg_loc_named()`.
+    pub fn arg_loc_named<T: OclPrm>(mut self, name: &'static str, length: usize) -> Kernel {
+        let arg_idx = self.new_arg_loc::<T>(length);
+        self.named_args.insert(name, arg_idx);
+        self
+    }
+
     /// Adds a new named argument (in order) specifying the value: `scalar`
     /// (builder-style).
     ///
@@ -298,10 +1116,32 @@ impl Kernel {
         self
     }
 
+    /// Adds a new argument (in order) without setting its value
+    /// (builder-style).
+    ///
+    /// The value must be set using `::set_arg` before the kernel is enqueued.
+    pub fn arg_unset(mut self) -> Kernel {
+        self.new_arg_unset();
+        self
+    }
+
+    /// Adds a new named argument (in order) without setting its value
+    /// (builder-style).
+    ///
+    /// The value must be set using `::set_arg` or one of the
+    /// `::set_arg_*_named` methods before the kernel is enqueued.
+    pub fn arg_unset_named(mut self, name: &'static str) -> Kernel {
+        let arg_idx = self.new_arg_unset();
+        self.named_args.insert(name, arg_idx);
+        self
+    }
+
     /// Modifies the kernel argument named: `name`.
     ///
-    /// ## Panics [FIXME]
-    // [FIXME]: CHECK THAT NAME EXISTS AND GIVE A BETTER ERROR MESSAGE
+    /// ## Errors
+    ///
+    /// Returns an error if no argument has been registered as `name` or if
+    /// the argument cannot be set (see `::set_arg`).
     pub fn set_arg_scl_named<'a, T: OclPrm>(&'a mut self, name: &'static str, scalar: T)
             -> OclResult<&'a mut Kernel>
     {
@@ -310,10 +1150,27 @@ impl Kernel {
             .and(Ok(self))
     }
 
+    /// Resizes the local memory allocation of the kernel argument named:
+    /// `name` to `length * sizeof(T)` bytes.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if no argument has been registered as `name` or if
+    /// the argument cannot be set (see `::set_arg_loc`).
+    pub fn set_arg_loc_named<'a, T: OclPrm>(&'a mut self, name: &'static str, length: usize)
+            -> OclResult<&'a mut Kernel>
+    {
+        let arg_idx = try!(self.resolve_named_arg_idx(name));
+        self.set_arg_loc::<T>(arg_idx, length)
+            .and(Ok(self))
+    }
+
     /// Modifies the kernel argument named: `name`.
     ///
-    /// ## Panics [FIXME]
-    // [FIXME]: CHECK THAT NAME EXISTS AND GIVE A BETTER ERROR MESSAGE
+    /// ## Errors
+    ///
+    /// Returns an error if no argument has been registered as `name` or if
+    /// the argument cannot be set (see `::set_arg`).
     pub fn set_arg_vec_named<'a, T: OclPrm>(&'a mut self, name: &'static str, vector: T)
             -> OclResult<&'a mut Kernel>
     {
@@ -324,14 +1181,15 @@ impl Kernel {
 
     /// Modifies the kernel argument named: `name`.
     ///
-    /// ## Panics [FIXME]
-    // [FIXME] TODO: CHECK THAT NAME EXISTS AND GIVE A BETTER ERROR MESSAGE
+    /// ## Errors
+    ///
+    /// Returns an error if no argument has been registered as `name` or if
+    /// the argument cannot be set (see `::set_arg`).
     pub fn set_arg_buf_named<'a, T, M>(&'a mut self, name: &'static str,
             buffer_opt: Option<M>)
             -> OclResult<&'a mut Kernel>
             where T: OclPrm, M: AsMemRef<T>
     {
-        //  TODO: ADD A CHECK FOR A VALID NAME (KEY)
         let arg_idx = try!(self.resolve_named_arg_idx(name));
         match buffer_opt {
             Some(buffer) => {
@@ -345,14 +1203,15 @@ impl Kernel {
 
     /// Modifies the kernel argument named: `name`.
     ///
-    /// ## Panics [FIXME]
-    // [FIXME] TODO: CHECK THAT NAME EXISTS AND GIVE A BETTER ERROR MESSAGE
+    /// ## Errors
+    ///
+    /// Returns an error if no argument has been registered as `name` or if
+    /// the argument cannot be set (see `::set_arg`).
     pub fn set_arg_img_named<'a, T, M>(&'a mut self, name: &'static str,
             image_opt: Option<M>)
             -> OclResult<&'a mut Kernel>
             where T: OclPrm, M: AsMemRef<T>
     {
-        //  TODO: ADD A CHECK FOR A VALID NAME (KEY)
         let arg_idx = try!(self.resolve_named_arg_idx(name));
         match image_opt {
             Some(img) => {
@@ -364,22 +1223,33 @@ impl Kernel {
         }.and(Ok(self))
     }
 
-    /// Sets the value of a named sampler argument.
+    /// Modifies the kernel argument named: `name`.
+    ///
+    /// ## Errors
     ///
-    /// ## Panics [FIXME]
-    // [PLACEHOLDER] Set a named sampler argument
-    #[allow(unused_variables)]
-    pub fn set_arg_smp_named<'a, T: OclPrm>(&'a mut self, name: &'static str,
-                sampler_opt: Option<&Sampler>) -> OclResult<&'a mut Kernel>
+    /// Returns an error if no argument has been registered as `name` or if
+    /// the argument cannot be set (see `::set_arg`).
+    pub fn set_arg_smp_named<'a>(&'a mut self, name: &'static str, sampler_opt: Option<&Sampler>)
+            -> OclResult<&'a mut Kernel>
     {
-        unimplemented!();
+        let arg_idx = try!(self.resolve_named_arg_idx(name));
+        match sampler_opt {
+            Some(sampler) => {
+                self.set_arg::<u8>(arg_idx, KernelArg::Sampler(sampler))
+            },
+            None => {
+                self.set_arg::<u8>(arg_idx, KernelArg::SamplerNull)
+            },
+        }.and(Ok(self))
     }
 
     /// Returns a command builder which is used to chain parameters of an
     /// 'enqueue' command together.
     pub fn cmd(&self) -> KernelCmd {
         KernelCmd { queue: &self.queue, kernel: &self.obj_core,
-            gwo: self.gwo, gws: self.gws, lws: self.lws,
+            gwo: self.gwo, gws: self.gws, lws: self.lws, auto_lws: self.auto_lws,
+            real_gws_arg: self.real_gws_arg, wg_limits: self.wg_limits.as_ref(),
+            arg_vals: &self.arg_vals[..], max_chunk_items: None, tracker: self.queue.tracker(),
             wait_list: None, dest_list: None }
     }
 
@@ -412,6 +1282,7 @@ impl Kernel {
     ///
     pub fn set_default_queue(&mut self, queue: Queue) -> OclResult<&mut Kernel> {
         // self.command_queue_obj_core = queue.core_as_ref().clone();
+        self.wg_limits = Some(try!(WorkGroupLimits::query(&self.obj_core, queue.core_as_ref())));
         self.queue = queue;
         Ok(self)
     }
@@ -442,6 +1313,25 @@ impl Kernel {
         self.arg_count
     }
 
+    /// Returns a description of the value set for each argument, in order.
+    ///
+    /// Arguments which have been declared (or which exist in the kernel)
+    /// but not yet set are described as `KernelArgKind::Unset`.
+    pub fn args(&self) -> Vec<KernelArgDesc> {
+        self.arg_vals.iter().enumerate().map(|(arg_idx, arg_val)| {
+            match *arg_val {
+                Some(ref arg_val) => arg_val.desc(arg_idx as u32),
+                None => KernelArgDesc {
+                    index: arg_idx as u32,
+                    kind: KernelArgKind::Unset,
+                    type_name: None,
+                    mem_len: None,
+                    value: None,
+                },
+            }
+        }).collect()
+    }
+
     /// Returns a reference to the core pointer wrapper, usable by functions in
     /// the `core` module.
     pub fn core_as_ref(&self) -> &KernelCore {
@@ -486,30 +1376,201 @@ impl Kernel {
         self.named_args.get(name).cloned()
     }
 
+    /// Returns the index of the parameter named `name` as declared within
+    /// the kernel source.
+    ///
+    /// Unlike `::named_arg_idx`, names do not need to have been registered
+    /// (with `::arg_buf_named`, etc.) but the program must have been built
+    /// with the `-cl-kernel-arg-info` compiler option.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error listing the kernel's parameters if no parameter is
+    /// named `name` or if parameter names are unavailable.
+    ///
+    pub fn arg_idx_by_name(&self, name: &str) -> OclResult<u32> {
+        let arg_types = match self.arg_types {
+            Some(ref arg_types) => arg_types,
+            None => return OclError::err(format!("ocl::Kernel::arg_idx_by_name(): Argument \
+                names are unavailable for kernel '{}'. Build the program with the \
+                '-cl-kernel-arg-info' compiler option.", self.name())),
+        };
+
+        match arg_types.iter().position(|arg_type| arg_type.name() == name) {
+            Some(arg_idx) => Ok(arg_idx as u32),
+            None => {
+                let arg_names: Vec<&str> = arg_types.iter().map(|at| at.name()).collect();
+                OclError::err(format!("ocl::Kernel::arg_idx_by_name(): Kernel '{}' has no \
+                    argument named '{}'. Arguments: [{}].", self.name(), name,
+                    arg_names.join(", ")))
+            },
+        }
+    }
+
+    /// Sets the argument named `name` as declared within the kernel source.
+    ///
+    /// Buffers, sub-buffers, images, and samplers can be passed directly
+    /// (`kernel.set_arg_by_name("dst", &buffer)`). Other kinds of arguments
+    /// can be passed as a `KernelArg` (`KernelArg::Scalar(1.0f32)`, etc.).
+    ///
+    /// See `::arg_idx_by_name` for more information.
+    ///
+    pub fn set_arg_by_name<'a, 'b, T, A>(&'a mut self, name: &str, arg: A)
+            -> OclResult<&'a mut Kernel>
+            where T: 'b + OclPrm, A: Into<KernelArg<'b, T>>
+    {
+        let arg_idx = try!(self.arg_idx_by_name(name));
+        self.set_arg::<T>(arg_idx, arg.into()).and(Ok(self))
+    }
+
     /// Sets an argument by index.
+    ///
+    /// ## Errors
+    ///
+    /// If the program was built with the `-cl-kernel-arg-info` option, the
+    /// argument is checked against the parameter declared in the kernel.
+    /// Binding a buffer to a non-pointer or an image to a buffer parameter,
+    /// a local allocation to a non-local parameter, or a value of a built-in
+    /// type which differs from the declared type (such as a `Buffer<u32>` to
+    /// a `float*` or a `u32` to a `ulong`) is an error.
+    ///
     pub fn set_arg<T: OclPrm>(&mut self, arg_idx: u32, arg: KernelArg<T>) -> OclResult<()> {
-        // If the `KernelArg` is a `Mem` variant, clone the `MemCore` it
-        // refers to, store it in `self.mem_args`, and create a new
-        // `KernelArg::Mem` refering to the locally stored copy. This prevents
-        // a buffer which has gone out of scope from being erroneously refered
-        // to when this kernel is enqueued and causing either a misleading
-        // error message or a hard to debug segfault depending on the
-        // platform.
-        let arg = match arg {
-            KernelArg::Mem(mem) => {
-                self.mem_args[arg_idx as usize] = Some(mem.clone());
-                let mem_arg_ref = self.mem_args.get(arg_idx as usize).unwrap().as_ref().unwrap();
-                KernelArg::Mem(mem_arg_ref)
-            },
-            arg => {
-                self.mem_args[arg_idx as usize] = None;
-                arg
-            },
+        // Checked against the declared argument count rather than
+        // `arg_vals`, which `::push_arg` may have grown to make room:
+        if arg_idx >= self.num_args {
+            return OclError::err(format!("ocl::Kernel::set_arg(): Argument index ({}) out of \
+                range (kernel: '{}', argument count: {}).", arg_idx, self.name(),
+                self.num_args));
+        }
+
+        if let Some(ref arg_types) = self.arg_types {
+            let arg_type = match arg_types.get(arg_idx as usize) {
+                Some(arg_type) => arg_type,
+                None => return OclError::err(format!("ocl::Kernel::set_arg(): No type \
+                    information for argument (kernel: '{}', index: {}).", self.name(), arg_idx)),
+            };
+
+            if let Err(err) = arg_type.check(&arg) {
+                return OclError::err(format!("ocl::Kernel::set_arg(): Invalid argument \
+                    (kernel: '{}', index: {}): {}", self.name(), arg_idx, err));
+            }
+        }
+
+        // Store a copy of the argument in `self.arg_vals`. If the
+        // `KernelArg` is a `Mem` or `Sampler` variant, create a new
+        // `KernelArg` refering to the locally stored copy. This prevents a
+        // buffer or sampler which has gone out of scope from being
+        // erroneously refered to when this kernel is enqueued and causing
+        // either a misleading error message or a hard to debug segfault
+        // depending on the platform.
+        self.arg_vals[arg_idx as usize] = Some(ArgVal::new(&arg));
+
+        let arg = match (arg, self.arg_vals[arg_idx as usize].as_ref()) {
+            (KernelArg::Mem(_), Some(&ArgVal::Mem(ref mem, _))) => KernelArg::Mem(mem),
+            (KernelArg::Sampler(_), Some(&ArgVal::Sampler(ref smp))) => KernelArg::Sampler(smp),
+            (arg, _) => arg,
         };
 
         core::set_kernel_arg::<T>(&self.obj_core, arg_idx, arg)
     }
 
+    /// Sets a sampler argument by index.
+    ///
+    /// The kernel retains its own reference to `sampler` so that it remains
+    /// valid until the argument is replaced or the kernel is dropped.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if the argument cannot be set (see `::set_arg`).
+    pub fn set_arg_smp(&mut self, arg_idx: u32, sampler: &Sampler) -> OclResult<()> {
+        self.set_arg::<u8>(arg_idx, KernelArg::Sampler(sampler))
+    }
+
+    /// Sets a local memory argument by index to an allocation of
+    /// `length * sizeof(T)` bytes.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if the kernel's total local memory usage
+    /// (`KernelWorkGroupInfo::LocalMemSize`, with this argument resized)
+    /// would exceed the device's `DeviceInfo::LocalMemSize` or if the
+    /// argument cannot be set (see `::set_arg`).
+    pub fn set_arg_loc<T: OclPrm>(&mut self, arg_idx: u32, length: usize) -> OclResult<()> {
+        try!(self.check_local_mem(arg_idx, length * std::mem::size_of::<T>()));
+        self.set_arg::<T>(arg_idx, KernelArg::Local(&length))
+    }
+
+    /// Returns a new kernel, independent of this one, with the same program,
+    /// name, arguments, named arguments, default queue, and work sizes.
+    ///
+    /// Arguments set on either kernel afterwards do not affect the other,
+    /// making duplicates suitable for use on separate threads.
+    ///
+    /// Uses `clCloneKernel` when the `opencl_version_2_1` feature is enabled
+    /// and the default queue's device supports OpenCL 2.1. Otherwise a new
+    /// kernel is created from the same program and each argument is set
+    /// again.
+    pub fn duplicate(&self) -> OclResult<Kernel> {
+        match try!(clone_kernel_core(self)) {
+            Some(obj_core) => Ok(self.with_core(obj_core, self.wg_limits.clone())),
+            None => {
+                let program = match self.info(KernelInfo::Program) {
+                    KernelInfoResult::Program(program) => program,
+                    KernelInfoResult::Error(err) => return Err(*err),
+                    _ => return OclError::err("ocl::Kernel::duplicate(): Unexpected \
+                        'KernelInfoResult' variant."),
+                };
+
+                let obj_core = try!(core::create_kernel(&program, &self.name()));
+                try!(self.replay_args(&obj_core));
+                Ok(self.with_core(obj_core, self.wg_limits.clone()))
+            },
+        }
+    }
+
+    /// Returns a new kernel with the same name, arguments, named arguments,
+    /// default queue, and work sizes, created from `program`.
+    ///
+    /// Useful to run the same workload using a program built from the same
+    /// source with different options (such as `-D` definitions). The kernel
+    /// must take the same arguments in `program`.
+    pub fn rebuild(&self, program: &Program) -> OclResult<Kernel> {
+        let obj_core = try!(core::create_kernel(program, &self.name()));
+        try!(self.replay_args(&obj_core));
+        let wg_limits = WorkGroupLimits::query(&obj_core, self.queue.core_as_ref()).ok();
+        Ok(self.with_core(obj_core, wg_limits))
+    }
+
+    /// Sets every argument set on this kernel on `obj_core`.
+    fn replay_args(&self, obj_core: &KernelCore) -> OclResult<()> {
+        for (arg_idx, arg_val) in self.arg_vals.iter().enumerate() {
+            if let Some(ref arg_val) = *arg_val {
+                try!(arg_val.set(obj_core, arg_idx as u32));
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns a copy of this kernel using `obj_core`, which must already
+    /// have had its arguments set.
+    fn with_core(&self, obj_core: KernelCore, wg_limits: Option<WorkGroupLimits>) -> Kernel {
+        Kernel {
+            obj_core: obj_core,
+            named_args: self.named_args.clone(),
+            arg_vals: self.arg_vals.clone(),
+            arg_types: self.arg_types.clone(),
+            arg_count: self.arg_count,
+            num_args: self.num_args,
+            queue: self.queue.clone(),
+            gwo: self.gwo,
+            gws: self.gws,
+            lws: self.lws,
+            auto_lws: self.auto_lws,
+            real_gws_arg: self.real_gws_arg,
+            wg_limits: wg_limits,
+        }
+    }
+
     fn fmt_info(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         f.debug_struct("Kernel")
             .field("FunctionName", &self.info(KernelInfo::FunctionName))
@@ -520,15 +1581,26 @@ impl Kernel {
             .finish()
     }
 
-    // fn fmt_arg_info(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-    //     f.debug_struct("Kernel")
-    //         .field("FunctionName", &self.info(KernelInfo::FunctionName))
-    //         .field("ReferenceCount", &self.info(KernelInfo::ReferenceCount))
-    //         .field("Context", &self.info(KernelInfo::Context))
-    //         .field("Program", &self.info(KernelInfo::Program))
-    //         .field("Attributes", &self.info(KernelInfo::Attributes))
-    //         .finish()
-    // }
+    fn fmt_arg_info(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let descs = self.args();
+        let mut list = f.debug_list();
+
+        for desc in descs.iter() {
+            // Names and declared types are only available when the program
+            // was built with '-cl-kernel-arg-info':
+            let (name, type_name) = match self.arg_types {
+                Some(ref arg_types) => {
+                    let arg_type = &arg_types[desc.index as usize];
+                    (Some(arg_type.name()), Some(arg_type.type_name()))
+                },
+                None => (None, None),
+            };
+
+            list.entry(&ArgInfoFmt { desc: desc, name: name, type_name: type_name });
+        }
+
+        list.finish()
+    }
 
     fn fmt_wg_info(&self, f: &mut std::fmt::Formatter, device: &Device) -> std::fmt::Result {
         f.debug_struct("WorkGroup")
@@ -542,13 +1614,52 @@ impl Kernel {
             .finish()
     }
 
+    /// Checks that resizing the local memory argument at `arg_idx` to `size`
+    /// bytes keeps the kernel within the device's local memory.
+    fn check_local_mem(&self, arg_idx: u32, size: usize) -> OclResult<()> {
+        let device = self.queue.device();
+
+        // Includes local memory arguments as currently set:
+        let kernel_mem = match self.wg_info(device, KernelWorkGroupInfo::LocalMemSize) {
+            KernelWorkGroupInfoResult::LocalMemSize(s) => s,
+            KernelWorkGroupInfoResult::Error(err) => return Err(*err),
+            _ => return OclError::err("ocl::Kernel::set_arg_loc(): Unexpected \
+                'KernelWorkGroupInfoResult' variant."),
+        };
+
+        let device_mem = match device.info(DeviceInfo::LocalMemSize) {
+            DeviceInfoResult::LocalMemSize(s) => s,
+            DeviceInfoResult::Error(err) => return Err(*err),
+            _ => return OclError::err("ocl::Kernel::set_arg_loc(): Unexpected \
+                'DeviceInfoResult' variant."),
+        };
+
+        let current_size = match self.arg_vals.get(arg_idx as usize) {
+            Some(&Some(ArgVal::Local(current_size, _))) => current_size,
+            _ => 0,
+        };
+
+        let total = kernel_mem.saturating_sub(current_size as u64) + size as u64;
+
+        if total > device_mem {
+            return OclError::err(format!("ocl::Kernel::set_arg_loc(): Local memory limit \
+                exceeded (kernel: '{}', index: {}): A {} byte allocation would bring the \
+                kernel's total local memory use to {} bytes ('LocalMemSize' of the device: {} \
+                bytes).", self.name(), arg_idx, size, total, device_mem));
+        }
+
+        Ok(())
+    }
+
     /// Resolves the index of a named argument.
     fn resolve_named_arg_idx(&self, name: &'static str) -> OclResult<u32> {
         match self.named_args.get(name) {
             Some(&ai) => Ok(ai),
             None => {
-                OclError::err(format!("Kernel::set_arg_scl_named(): Invalid argument \
-                    name: '{}'.", name))
+                let mut names: Vec<&str> = self.named_args.keys().cloned().collect();
+                names.sort();
+                OclError::err(format!("ocl::Kernel: Invalid argument name: '{}' (kernel: '{}'). \
+                    Registered argument names: [{}].", name, self.name(), names.join(", ")))
             },
         }
     }
@@ -624,24 +1735,47 @@ impl Kernel {
         self.new_arg::<T>(KernelArg::Local(&length))
     }
 
+    /// Adds a new argument with no value to the kernel and returns the index.
+    fn new_arg_unset(&mut self) -> u32 {
+        self.push_arg(|_, _| Ok(())).expect("Kernel::new_arg_unset")
+    }
+
     /// Adds a new argument to the kernel and returns the index.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if the argument cannot be set. Use `KernelBuilder` to avoid.
     fn new_arg<T: OclPrm>(&mut self, arg: KernelArg<T>) -> u32 {
-        let arg_idx = self.arg_count;
-
-        // Push an empty `mem_arg` to the list just to make room.
-        self.mem_args.push(None);
-
-        match self.set_arg(arg_idx, arg) {
-            Ok(_) => (),
+        match self.push_arg(|k, arg_idx| k.set_arg(arg_idx, arg)) {
+            Ok(arg_idx) => arg_idx,
             Err(err) => {
                 panic!("Kernel::new_arg(arg_idx: {}): {}",
-                    arg_idx, err);
+                    self.arg_count, err);
             }
         }
+    }
+
+    /// Adds a new argument to the kernel using `set` and returns the index.
+    fn push_arg<F>(&mut self, set: F) -> OclResult<u32>
+            where F: FnOnce(&mut Kernel, u32) -> OclResult<()>
+    {
+        let arg_idx = self.arg_count;
+
+        // Push an empty `arg_val` to the list just to make room (unless
+        // room was made for every argument on creation).
+        let make_room = self.arg_vals.len() <= arg_idx as usize;
+        if make_room {
+            self.arg_vals.push(None);
+        }
+
+        if let Err(err) = set(self, arg_idx) {
+            if make_room { self.arg_vals.pop(); }
+            return Err(err);
+        }
 
         self.arg_count += 1;
-        debug_assert!(self.arg_count as usize == self.mem_args.len());
-        arg_idx
+        debug_assert!(self.arg_count as usize <= self.arg_vals.len());
+        Ok(arg_idx)
     }
 }
 
@@ -651,7 +1785,32 @@ impl std::fmt::Display for Kernel {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         try!(self.fmt_info(f));
         try!(write!(f, " "));
-        self.fmt_wg_info(f, self.queue.device())
+        try!(self.fmt_wg_info(f, self.queue.device()));
+        try!(write!(f, " Args"));
+        self.fmt_arg_info(f)
+    }
+}
+
+
+/// Formats a kernel argument description along with its declared name and
+/// type.
+struct ArgInfoFmt<'a> {
+    desc: &'a KernelArgDesc,
+    name: Option<&'a str>,
+    type_name: Option<&'a str>,
+}
+
+impl<'a> std::fmt::Debug for ArgInfoFmt<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("Arg")
+            .field("Index", &self.desc.index)
+            .field("Name", &self.name)
+            .field("TypeName", &self.type_name)
+            .field("Kind", &self.desc.kind)
+            .field("RustType", &self.desc.type_name)
+            .field("MemLen", &self.desc.mem_len)
+            .field("Value", &self.desc.value)
+            .finish()
     }
 }
 
@@ -670,3 +1829,134 @@ impl DerefMut for Kernel {
         &mut self.obj_core
     }
 }
+
+macro_rules! impl_kernel_arg_value {
+    ($variant:ident: $($ty:ty),*) => ($(
+        impl KernelArgValue for $ty {
+            fn set_kernel_arg(&self, kernel: &mut Kernel, arg_idx: u32) -> OclResult<()> {
+                kernel.set_arg::<$ty>(arg_idx, KernelArg::$variant(*self))
+            }
+        }
+    )*)
+}
+
+impl_kernel_arg_value!(Scalar: i8, u8, i16, u16, i32, u32, i64, u64, isize, usize, f32, f64);
+
+impl_kernel_arg_value!(Vector: ClChar2, ClChar3, ClChar4, ClChar8, ClChar16,
+    ClUchar2, ClUchar3, ClUchar4, ClUchar8, ClUchar16,
+    ClShort2, ClShort3, ClShort4, ClShort8, ClShort16,
+    ClUshort2, ClUshort3, ClUshort4, ClUshort8, ClUshort16,
+    ClInt2, ClInt3, ClInt4, ClInt8, ClInt16,
+    ClUint2, ClUint3, ClUint4, ClUint8, ClUint16,
+    ClLong1, ClLong2, ClLong3, ClLong4, ClLong8, ClLong16,
+    ClUlong1, ClUlong2, ClUlong3, ClUlong4, ClUlong8, ClUlong16,
+    ClFloat2, ClFloat3, ClFloat4, ClFloat8, ClFloat16,
+    ClDouble2, ClDouble3, ClDouble4, ClDouble8, ClDouble16);
+
+
+
+/// A pool of duplicates of a kernel, able to be shared between threads.
+///
+/// `::get` returns a `PooledKernel` which can be used exactly like a
+/// `Kernel` and is returned to the pool when dropped. Kernels are created
+/// (using `Kernel::duplicate`) only when none are available. Arguments set
+/// on a pooled kernel persist when it is returned to the pool.
+///
+/// ## Examples
+///
+/// ```text
+/// let pool = Arc::new(KernelPool::new(kernel));
+///
+/// let pool_clone = pool.clone();
+/// thread::spawn(move || {
+///     let mut kernel = pool_clone.get().unwrap();
+///     kernel.set_arg_scl_named("offset", 1024u32).unwrap();
+///     kernel.enq().unwrap();
+/// });
+/// ```
+///
+pub struct KernelPool {
+    template: Mutex<Kernel>,
+    idle: Mutex<Vec<Kernel>>,
+}
+
+impl KernelPool {
+    /// Returns a new pool of duplicates of `kernel`.
+    pub fn new(kernel: Kernel) -> KernelPool {
+        KernelPool {
+            template: Mutex::new(kernel),
+            idle: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Returns a new pool containing `count` duplicates of `kernel`, ready
+    /// for use.
+    pub fn with_count(kernel: Kernel, count: usize) -> OclResult<KernelPool> {
+        let mut idle = Vec::with_capacity(count);
+
+        for _ in 0..count {
+            idle.push(try!(kernel.duplicate()));
+        }
+
+        Ok(KernelPool {
+            template: Mutex::new(kernel),
+            idle: Mutex::new(idle),
+        })
+    }
+
+    /// Returns an available kernel, duplicating a new one if none are
+    /// available.
+    pub fn get(&self) -> OclResult<PooledKernel> {
+        let kernel = match self.idle.lock().unwrap().pop() {
+            Some(kernel) => kernel,
+            None => try!(self.template.lock().unwrap().duplicate()),
+        };
+
+        Ok(PooledKernel { kernel: Some(kernel), pool: self })
+    }
+
+    /// Returns the number of kernels not currently in use.
+    pub fn idle_count(&self) -> usize {
+        self.idle.lock().unwrap().len()
+    }
+}
+
+impl std::fmt::Debug for KernelPool {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("KernelPool")
+            .field("idle_count", &self.idle_count())
+            .finish()
+    }
+}
+
+
+/// A kernel borrowed from a `KernelPool`, returned to it when dropped.
+#[derive(Debug)]
+pub struct PooledKernel<'p> {
+    kernel: Option<Kernel>,
+    pool: &'p KernelPool,
+}
+
+impl<'p> Deref for PooledKernel<'p> {
+    type Target = Kernel;
+
+    fn deref(&self) -> &Kernel {
+        self.kernel.as_ref().unwrap()
+    }
+}
+
+impl<'p> DerefMut for PooledKernel<'p> {
+    fn deref_mut(&mut self) -> &mut Kernel {
+        self.kernel.as_mut().unwrap()
+    }
+}
+
+impl<'p> Drop for PooledKernel<'p> {
+    fn drop(&mut self) {
+        if let Some(kernel) = self.kernel.take() {
+            if let Ok(mut idle) = self.pool.idle.lock() {
+                idle.push(kernel);
+            }
+        }
+    }
+}
@@ -10,18 +10,28 @@ mod context;
 // mod program_builder;
 mod program;
 mod kernel;
+mod arg_type;
 mod queue;
 mod buffer;
 // mod buffer_cmd;
 // mod image_builder;
 mod image;
+mod pixel;
 // mod image_cmd;
 mod sampler;
+mod reference;
 // mod pro_que_builder;
 mod pro_que;
 mod event;
 // mod event_list;
 mod spatial_dims;
+mod work_group;
+mod tune;
+mod host_fn;
+mod tracker;
+mod profiler;
+mod timeline;
+#[cfg(feature = "future")] mod future;
 // mod work_dims;
 
 // #[cfg(not(release))] pub use self::buffer::tests::BufferTest;
@@ -33,19 +43,28 @@ pub use self::context::{Context, ContextBuilder};
 // pub use self::program_builder::{ProgramBuilder, BuildOpt};
 pub use self::program::{Program, ProgramBuilder, BuildOpt};
 pub use self::queue::Queue;
-pub use self::kernel::{Kernel, KernelCmd};
+pub use self::tracker::{ResourceTracker, Resource};
+pub use self::profiler::{Profiler, ProfileStats};
+pub use self::timeline::Timeline;
+pub use self::kernel::{Kernel, KernelCmd, KernelBuilder, KernelPool, PooledKernel, KernelArgDesc,
+    KernelArgKind};
 pub use self::buffer::{MappedMem, BufferCmdKind, BufferCmdDataShape, BufferCmd, Buffer, SubBuffer};
 // pub use self::buffer_cmd::{BufferCmd, BufferCmdKind, BufferCmdDataShape};
 // pub use self::image_builder::ImageBuilder;
-pub use self::image::{Image, ImageCmd, ImageCmdKind, ImageBuilder};
+pub use self::image::{Image, ImageCmd, ImageCmdKind, ImageBuilder, ImageLayer,
+    ImageProperties};
+pub use self::pixel::PixelConverter;
 // pub use self::image_cmd::{ImageCmd, ImageCmdKind};
-pub use self::sampler::Sampler;
+pub use self::sampler::{Sampler, SamplerBuilder, SamplerProperties};
+pub use self::reference::HostSampler;
 // pub use self::pro_que_builder::ProQueBuilder;
 pub use self::pro_que::{ProQue, ProQueBuilder};
-pub use self::event::{Event, EventList};
+pub use self::event::{Event, EventList, EventListIter, UserEvent, EventTimings};
+#[cfg(feature = "future")] pub use self::future::{EventFuture, EventListFuture, ReadFuture};
 // pub use self::event_list::EventList;
 pub use self::spatial_dims::SpatialDims;
-pub use self::traits::{MemLen, WorkDims, AsMemRef, AsMemMut};
+pub use self::tune::{Tuner, TuneConfig, Tuned, CACHE_PATH_VAR as TUNE_CACHE_PATH_VAR};
+pub use self::traits::{MemLen, WorkDims, AsMemRef, AsMemMut, KernelArgValue};
 
 
 //=============================================================================
@@ -70,7 +89,8 @@ mod traits {
     use num::{Num, ToPrimitive};
     // use core::error::{Result as OclResult};
     use core::Mem as MemCore;
-    use ::{SpatialDims, OclPrm};
+    use core::error::Result as OclResult;
+    use ::{SpatialDims, OclPrm, Kernel};
     use super::spatial_dims::to_usize;
 
 
@@ -86,6 +106,18 @@ mod traits {
     }
 
 
+    /// Types which can be used as the value of a kernel argument declared
+    /// using the `ocl_kernel!` macro.
+    ///
+    /// Implemented for references to buffers, sub-buffers, images, and
+    /// samplers and for the scalar and vector primitive types. Implement it
+    /// for custom `OclPrm` types to use them with `ocl_kernel!`.
+    pub trait KernelArgValue {
+        /// Sets the argument at `arg_idx` of `kernel` to this value.
+        fn set_kernel_arg(&self, kernel: &mut Kernel, arg_idx: u32) -> OclResult<()>;
+    }
+
+
     /// Types which have properties describing the amount of work to be done
     /// in multiple dimensions.
     ///
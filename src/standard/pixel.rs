@@ -0,0 +1,484 @@
+//! Host-side conversion between floating point pixels and image formats.
+
+use std::mem;
+use core::error::{Error as OclError, Result as OclResult};
+use core::{ImageFormat, ImageChannelOrder, ImageChannelDataType};
+use standard::reference::rint;
+
+/// The number of components in each unpacked pixel (always four: RGBA).
+const PIXEL_COMPONENTS: usize = 4;
+
+/// The size in bytes of the four component color passed to
+/// `clEnqueueFillImage` (`float4`, `int4`, or `uint4`).
+pub const FILL_COLOR_SIZE: usize = 16;
+
+/// A component of an unpacked (RGBA) pixel or unused padding.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Comp {
+    R,
+    G,
+    B,
+    A,
+    Pad,
+}
+
+/// Returns the components stored in memory, in order, for a channel order.
+fn channel_layout(order: ImageChannelOrder) -> OclResult<&'static [Comp]> {
+    use self::Comp::*;
+
+    match order {
+        ImageChannelOrder::R => Ok(&[R]),
+        ImageChannelOrder::A => Ok(&[A]),
+        ImageChannelOrder::Rg => Ok(&[R, G]),
+        ImageChannelOrder::Ra => Ok(&[R, A]),
+        ImageChannelOrder::Rgb => Ok(&[R, G, B]),
+        ImageChannelOrder::Rgba => Ok(&[R, G, B, A]),
+        ImageChannelOrder::Bgra => Ok(&[B, G, R, A]),
+        ImageChannelOrder::Argb => Ok(&[A, R, G, B]),
+        ImageChannelOrder::Intensity => Ok(&[R]),
+        ImageChannelOrder::Luminance => Ok(&[R]),
+        ImageChannelOrder::Rx => Ok(&[R, Pad]),
+        ImageChannelOrder::Rgx => Ok(&[R, G, Pad]),
+        ImageChannelOrder::Rgbx => Ok(&[R, G, B, Pad]),
+        order => OclError::err(format!("ocl::PixelConverter: Unsupported channel order: {:?}.",
+            order)),
+    }
+}
+
+/// Returns the size in bytes of a single channel or `None` for packed
+/// (multiple channels per value) data types.
+fn channel_size(data_type: ImageChannelDataType) -> OclResult<Option<usize>> {
+    match data_type {
+        ImageChannelDataType::SnormInt8 | ImageChannelDataType::UnormInt8 |
+            ImageChannelDataType::SignedInt8 | ImageChannelDataType::UnsignedInt8 => Ok(Some(1)),
+        ImageChannelDataType::SnormInt16 | ImageChannelDataType::UnormInt16 |
+            ImageChannelDataType::SignedInt16 | ImageChannelDataType::UnsignedInt16 |
+            ImageChannelDataType::HalfFloat => Ok(Some(2)),
+        ImageChannelDataType::SignedInt32 | ImageChannelDataType::UnsignedInt32 |
+            ImageChannelDataType::Float => Ok(Some(4)),
+        ImageChannelDataType::UnormShort565 | ImageChannelDataType::UnormShort555 |
+            ImageChannelDataType::UnormInt101010 => Ok(None),
+        data_type => OclError::err(format!("ocl::PixelConverter: Unsupported channel data \
+            type: {:?}.", data_type)),
+    }
+}
+
+
+/// Converts between four component (RGBA) `f32` pixels and the in-memory
+/// representation of a particular `ImageFormat`.
+///
+/// Packing follows the conversion rules used by `write_imagef`,
+/// `write_imagei`, and `write_imageui` as described in section 8.3 of the
+/// [OpenCL 1.2 specification]:
+///
+/// - Normalized integer types are scaled (by 255, 32767, etc.) then rounded
+///   to nearest even and saturated. `NaN` becomes zero.
+/// - Unnormalized integer types are rounded to nearest even and saturated.
+/// - `HalfFloat` is rounded to nearest even. Values too large to be
+///   represented become infinity.
+/// - Packed types (`UnormShort565`, `UnormShort555`, and `UnormInt101010`)
+///   require a channel order of `Rgb` or `Rgbx`.
+///
+/// Unpacking follows the rules used by `read_image{f|i|ui}`: missing
+/// components are filled in with `(0, 0, 0, 1)`, `Intensity` is replicated
+/// to all four components, and `Luminance` to red, green, and blue. When
+/// packing `Intensity` or `Luminance` the red component is used.
+///
+/// Multi-byte values use the byte order of the host.
+///
+/// [OpenCL 1.2 specification]: https://www.khronos.org/registry/cl/specs/opencl-1.2.pdf
+///
+#[derive(Clone, Debug)]
+pub struct PixelConverter {
+    format: ImageFormat,
+    layout: &'static [Comp],
+    channel_size: Option<usize>,
+    pixel_size: usize,
+}
+
+impl PixelConverter {
+    /// Returns a new converter for `format`.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if the channel order or data type is not supported
+    /// or if they cannot be used together.
+    ///
+    pub fn new(format: ImageFormat) -> OclResult<PixelConverter> {
+        let layout = try!(channel_layout(format.channel_order));
+        let channel_size = try!(channel_size(format.channel_data_type));
+
+        let pixel_size = match channel_size {
+            Some(size) => {
+                if let ImageChannelOrder::Rgb = format.channel_order {
+                    return OclError::err(format!("ocl::PixelConverter::new: The 'Rgb' channel \
+                        order may only be used with packed data types ('UnormShort565', \
+                        'UnormShort555', or 'UnormInt101010'), not '{:?}'.",
+                        format.channel_data_type));
+                }
+                size * layout.len()
+            },
+            None => {
+                match format.channel_order {
+                    ImageChannelOrder::Rgb | ImageChannelOrder::Rgbx => (),
+                    order => return OclError::err(format!("ocl::PixelConverter::new: Packed \
+                        data types ('{:?}') may only be used with the 'Rgb' or 'Rgbx' channel \
+                        orders, not '{:?}'.", format.channel_data_type, order)),
+                }
+                match format.channel_data_type {
+                    ImageChannelDataType::UnormInt101010 => 4,
+                    _ => 2,
+                }
+            },
+        };
+
+        Ok(PixelConverter {
+            format: format,
+            layout: layout,
+            channel_size: channel_size,
+            pixel_size: pixel_size,
+        })
+    }
+
+    /// Returns the image format.
+    pub fn format(&self) -> &ImageFormat {
+        &self.format
+    }
+
+    /// Returns the size in bytes of a single packed pixel.
+    pub fn pixel_size(&self) -> usize {
+        self.pixel_size
+    }
+
+    /// Packs RGBA `pixels` into a new byte vector.
+    ///
+    /// ## Errors
+    ///
+    /// `pixels.len()` must be a multiple of four.
+    ///
+    pub fn pack(&self, pixels: &[f32]) -> OclResult<Vec<u8>> {
+        if pixels.len() % PIXEL_COMPONENTS != 0 {
+            return OclError::err(format!("ocl::PixelConverter::pack: The pixel data length \
+                ({}) must be a multiple of four (RGBA).", pixels.len()));
+        }
+
+        let mut bytes = Vec::with_capacity((pixels.len() / PIXEL_COMPONENTS) * self.pixel_size);
+
+        for pixel in pixels.chunks(PIXEL_COMPONENTS) {
+            match self.channel_size {
+                Some(_) => {
+                    for &comp in self.layout {
+                        let val = match comp {
+                            Comp::R => pixel[0],
+                            Comp::G => pixel[1],
+                            Comp::B => pixel[2],
+                            Comp::A => pixel[3],
+                            Comp::Pad => 0.0,
+                        };
+                        encode_channel(self.format.channel_data_type, val, &mut bytes);
+                    }
+                },
+                None => encode_packed(self.format.channel_data_type, pixel, &mut bytes),
+            }
+        }
+
+        Ok(bytes)
+    }
+
+    /// Unpacks `bytes` into a new vector of RGBA pixels.
+    ///
+    /// ## Errors
+    ///
+    /// `bytes.len()` must be a multiple of `::pixel_size`.
+    ///
+    pub fn unpack(&self, bytes: &[u8]) -> OclResult<Vec<f32>> {
+        let mut pixels = vec![0.0f32; (bytes.len() / self.pixel_size) * PIXEL_COMPONENTS];
+        try!(self.unpack_into(bytes, &mut pixels));
+        Ok(pixels)
+    }
+
+    /// Unpacks `bytes` into `pixels`.
+    ///
+    /// ## Errors
+    ///
+    /// `pixels` must contain exactly four components for each packed pixel
+    /// in `bytes`.
+    ///
+    pub fn unpack_into(&self, bytes: &[u8], pixels: &mut [f32]) -> OclResult<()> {
+        if bytes.len() % self.pixel_size != 0 ||
+            pixels.len() != (bytes.len() / self.pixel_size) * PIXEL_COMPONENTS
+        {
+            return OclError::err(format!("ocl::PixelConverter::unpack: The packed data length \
+                ({} bytes) must be a multiple of the pixel size ({} bytes) and the unpacked \
+                data length ({}) must be four (RGBA) per pixel.", bytes.len(), self.pixel_size,
+                pixels.len()));
+        }
+
+        for (src, pixel) in bytes.chunks(self.pixel_size).zip(pixels.chunks_mut(PIXEL_COMPONENTS)) {
+            let mut rgba = [0.0, 0.0, 0.0, 1.0];
+
+            match self.channel_size {
+                Some(size) => {
+                    for (&comp, chan) in self.layout.iter().zip(src.chunks(size)) {
+                        let val = decode_channel(self.format.channel_data_type, chan);
+                        match comp {
+                            Comp::R => rgba[0] = val,
+                            Comp::G => rgba[1] = val,
+                            Comp::B => rgba[2] = val,
+                            Comp::A => rgba[3] = val,
+                            Comp::Pad => (),
+                        }
+                    }
+                },
+                None => decode_packed(self.format.channel_data_type, src, &mut rgba),
+            }
+
+            match self.format.channel_order {
+                ImageChannelOrder::Intensity => rgba = [rgba[0]; 4],
+                ImageChannelOrder::Luminance => rgba = [rgba[0], rgba[0], rgba[0], 1.0],
+                _ => (),
+            }
+
+            pixel.copy_from_slice(&rgba);
+        }
+
+        Ok(())
+    }
+}
+
+
+/// Returns the color passed to `clEnqueueFillImage` to fill an image with
+/// the packed pixel, `pixel`, of the format used by `converter`.
+///
+/// The color is a `float4` for normalized and floating point data types, an
+/// `int4` for signed integer types, and a `uint4` for unsigned integer types
+/// (section 5.3.4 of the OpenCL 1.2 specification). Integer channels are
+/// converted exactly rather than by way of an `f32`.
+pub fn fill_color(converter: &PixelConverter, pixel: &[u8]) -> OclResult<[u8; FILL_COLOR_SIZE]> {
+    if pixel.len() != converter.pixel_size {
+        return OclError::err(format!("ocl::PixelConverter: The fill pixel length ({} bytes) \
+            must equal the pixel size ({} bytes).", pixel.len(), converter.pixel_size));
+    }
+
+    let data_type = converter.format.channel_data_type;
+
+    let rgba: [u32; 4] = match (data_type, converter.channel_size) {
+        (ImageChannelDataType::SignedInt8, Some(size)) |
+        (ImageChannelDataType::SignedInt16, Some(size)) |
+        (ImageChannelDataType::SignedInt32, Some(size)) |
+        (ImageChannelDataType::UnsignedInt8, Some(size)) |
+        (ImageChannelDataType::UnsignedInt16, Some(size)) |
+        (ImageChannelDataType::UnsignedInt32, Some(size)) => {
+            let mut rgba = [0, 0, 0, 1];
+
+            for (&comp, chan) in converter.layout.iter().zip(pixel.chunks(size)) {
+                let val = decode_int_channel(data_type, chan);
+                match comp {
+                    Comp::R => rgba[0] = val,
+                    Comp::G => rgba[1] = val,
+                    Comp::B => rgba[2] = val,
+                    Comp::A => rgba[3] = val,
+                    Comp::Pad => (),
+                }
+            }
+
+            match converter.format.channel_order {
+                ImageChannelOrder::Intensity => [rgba[0]; 4],
+                ImageChannelOrder::Luminance => [rgba[0], rgba[0], rgba[0], 1],
+                _ => rgba,
+            }
+        },
+        _ => {
+            let floats = try!(converter.unpack(pixel));
+            let mut rgba = [0; 4];
+            for (bits, &val) in rgba.iter_mut().zip(floats.iter()) {
+                *bits = unsafe { mem::transmute(val) };
+            }
+            rgba
+        },
+    };
+
+    let mut color = Vec::with_capacity(FILL_COLOR_SIZE);
+    for &val in rgba.iter() {
+        push_u32(&mut color, val);
+    }
+
+    let mut color_arr = [0u8; FILL_COLOR_SIZE];
+    color_arr.copy_from_slice(&color);
+    Ok(color_arr)
+}
+
+
+/// Rounds to nearest even then saturates to `[min, max]`. `NaN` becomes zero.
+fn sat_rte(val: f32, min: f64, max: f64) -> f64 {
+    if val.is_nan() { return 0.0; }
+    let r = rint(val) as f64;
+    if r < min { min } else if r > max { max } else { r }
+}
+
+/// Converts to a normalized unsigned integer with `max` as 1.0.
+fn to_unorm(val: f32, max: u32) -> u32 {
+    sat_rte(val * max as f32, 0.0, max as f64) as u32
+}
+
+fn from_unorm(val: u32, max: u32) -> f32 {
+    val as f32 / max as f32
+}
+
+fn push_u16(bytes: &mut Vec<u8>, val: u16) {
+    let b: [u8; 2] = unsafe { mem::transmute(val) };
+    bytes.extend_from_slice(&b);
+}
+
+fn push_u32(bytes: &mut Vec<u8>, val: u32) {
+    let b: [u8; 4] = unsafe { mem::transmute(val) };
+    bytes.extend_from_slice(&b);
+}
+
+fn read_u16(bytes: &[u8]) -> u16 {
+    unsafe { mem::transmute([bytes[0], bytes[1]]) }
+}
+
+fn read_u32(bytes: &[u8]) -> u32 {
+    unsafe { mem::transmute([bytes[0], bytes[1], bytes[2], bytes[3]]) }
+}
+
+fn encode_channel(data_type: ImageChannelDataType, val: f32, bytes: &mut Vec<u8>) {
+    match data_type {
+        ImageChannelDataType::UnormInt8 => bytes.push(to_unorm(val, 255) as u8),
+        ImageChannelDataType::UnormInt16 => push_u16(bytes, to_unorm(val, 65535) as u16),
+        ImageChannelDataType::SnormInt8 => {
+            bytes.push(sat_rte(val * 127.0, -128.0, 127.0) as i8 as u8)
+        },
+        ImageChannelDataType::SnormInt16 => {
+            push_u16(bytes, sat_rte(val * 32767.0, -32768.0, 32767.0) as i16 as u16)
+        },
+        ImageChannelDataType::SignedInt8 => bytes.push(sat_rte(val, -128.0, 127.0) as i8 as u8),
+        ImageChannelDataType::SignedInt16 => {
+            push_u16(bytes, sat_rte(val, -32768.0, 32767.0) as i16 as u16)
+        },
+        ImageChannelDataType::SignedInt32 => {
+            push_u32(bytes, sat_rte(val, -2147483648.0, 2147483647.0) as i32 as u32)
+        },
+        ImageChannelDataType::UnsignedInt8 => bytes.push(sat_rte(val, 0.0, 255.0) as u8),
+        ImageChannelDataType::UnsignedInt16 => push_u16(bytes, sat_rte(val, 0.0, 65535.0) as u16),
+        ImageChannelDataType::UnsignedInt32 => {
+            push_u32(bytes, sat_rte(val, 0.0, 4294967295.0) as u32)
+        },
+        ImageChannelDataType::HalfFloat => push_u16(bytes, f32_to_f16(val)),
+        ImageChannelDataType::Float => push_u32(bytes, unsafe { mem::transmute(val) }),
+        _ => unreachable!(),
+    }
+}
+
+fn decode_channel(data_type: ImageChannelDataType, bytes: &[u8]) -> f32 {
+    match data_type {
+        ImageChannelDataType::UnormInt8 => from_unorm(bytes[0] as u32, 255),
+        ImageChannelDataType::UnormInt16 => from_unorm(read_u16(bytes) as u32, 65535),
+        ImageChannelDataType::SnormInt8 => (bytes[0] as i8 as f32 / 127.0).max(-1.0),
+        ImageChannelDataType::SnormInt16 => (read_u16(bytes) as i16 as f32 / 32767.0).max(-1.0),
+        ImageChannelDataType::SignedInt8 => bytes[0] as i8 as f32,
+        ImageChannelDataType::SignedInt16 => read_u16(bytes) as i16 as f32,
+        ImageChannelDataType::SignedInt32 => read_u32(bytes) as i32 as f32,
+        ImageChannelDataType::UnsignedInt8 => bytes[0] as f32,
+        ImageChannelDataType::UnsignedInt16 => read_u16(bytes) as f32,
+        ImageChannelDataType::UnsignedInt32 => read_u32(bytes) as f32,
+        ImageChannelDataType::HalfFloat => f16_to_f32(read_u16(bytes)),
+        ImageChannelDataType::Float => unsafe { mem::transmute(read_u32(bytes)) },
+        _ => unreachable!(),
+    }
+}
+
+/// Decodes an unnormalized integer channel, sign extending signed types to
+/// 32 bits.
+fn decode_int_channel(data_type: ImageChannelDataType, bytes: &[u8]) -> u32 {
+    match data_type {
+        ImageChannelDataType::SignedInt8 => bytes[0] as i8 as i32 as u32,
+        ImageChannelDataType::SignedInt16 => read_u16(bytes) as i16 as i32 as u32,
+        ImageChannelDataType::UnsignedInt8 => bytes[0] as u32,
+        ImageChannelDataType::UnsignedInt16 => read_u16(bytes) as u32,
+        ImageChannelDataType::SignedInt32 | ImageChannelDataType::UnsignedInt32 => read_u32(bytes),
+        _ => unreachable!(),
+    }
+}
+
+fn encode_packed(data_type: ImageChannelDataType, pixel: &[f32], bytes: &mut Vec<u8>) {
+    match data_type {
+        ImageChannelDataType::UnormShort565 => push_u16(bytes, ((to_unorm(pixel[0], 31) << 11) |
+            (to_unorm(pixel[1], 63) << 5) | to_unorm(pixel[2], 31)) as u16),
+        ImageChannelDataType::UnormShort555 => push_u16(bytes, ((to_unorm(pixel[0], 31) << 10) |
+            (to_unorm(pixel[1], 31) << 5) | to_unorm(pixel[2], 31)) as u16),
+        ImageChannelDataType::UnormInt101010 => push_u32(bytes, (to_unorm(pixel[0], 1023) << 20) |
+            (to_unorm(pixel[1], 1023) << 10) | to_unorm(pixel[2], 1023)),
+        _ => unreachable!(),
+    }
+}
+
+fn decode_packed(data_type: ImageChannelDataType, bytes: &[u8], rgba: &mut [f32; 4]) {
+    let (val, shifts, maxes) = match data_type {
+        ImageChannelDataType::UnormShort565 => (read_u16(bytes) as u32, [11, 5, 0], [31, 63, 31]),
+        ImageChannelDataType::UnormShort555 => (read_u16(bytes) as u32, [10, 5, 0], [31, 31, 31]),
+        ImageChannelDataType::UnormInt101010 => (read_u32(bytes), [20, 10, 0], [1023, 1023, 1023]),
+        _ => unreachable!(),
+    };
+
+    for c in 0..3 {
+        rgba[c] = from_unorm((val >> shifts[c]) & maxes[c], maxes[c]);
+    }
+}
+
+/// Converts to a half precision float, rounding to nearest even.
+fn f32_to_f16(val: f32) -> u16 {
+    let bits: u32 = unsafe { mem::transmute(val) };
+    let sign = (bits >> 16) & 0x8000;
+    let exp = ((bits >> 23) & 0xff) as i32;
+    let man = bits & 0x7f_ffff;
+
+    // Infinity and NaN (preserving a quiet NaN):
+    if exp == 0xff {
+        return (sign | 0x7c00 | if man != 0 { 0x0200 | (man >> 13) } else { 0 }) as u16;
+    }
+
+    let half_exp = exp - 127 + 15;
+
+    // Overflow:
+    if half_exp >= 0x1f {
+        return (sign | 0x7c00) as u16;
+    }
+
+    // Subnormal or zero:
+    if half_exp <= 0 {
+        if half_exp < -10 {
+            return sign as u16;
+        }
+        let man = man | 0x80_0000;
+        let shift = (14 - half_exp) as u32;
+        let half_man = man >> shift;
+        let rem = man & ((1 << shift) - 1);
+        let halfway = 1 << (shift - 1);
+        let round_up = rem > halfway || (rem == halfway && half_man & 1 == 1);
+        return (sign | (half_man + round_up as u32)) as u16;
+    }
+
+    // Normal (a carry out of the mantissa correctly increments the exponent):
+    let half = sign | ((half_exp as u32) << 10) | (man >> 13);
+    let rem = man & 0x1fff;
+    let round_up = rem > 0x1000 || (rem == 0x1000 && half & 1 == 1);
+    (half + round_up as u32) as u16
+}
+
+/// Converts from a half precision float.
+fn f16_to_f32(half: u16) -> f32 {
+    let sign = ((half & 0x8000) as u32) << 16;
+    let exp = ((half >> 10) & 0x1f) as u32;
+    let man = (half & 0x3ff) as u32;
+
+    match exp {
+        0 => {
+            let val = man as f32 * (2.0f32).powi(-24);
+            if sign == 0 { val } else { -val }
+        },
+        0x1f => unsafe { mem::transmute(sign | 0x7f80_0000 | (man << 13)) },
+        _ => unsafe { mem::transmute(sign | ((exp + 112) << 23) | (man << 13)) },
+    }
+}
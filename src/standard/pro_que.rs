@@ -5,7 +5,7 @@ use std::ops::Deref;
 use core::error::{Result as OclResult, Error as OclError};
 use core::{OclPrm, CommandQueueProperties};
 use standard::{Platform, Device, Context, ProgramBuilder, Program, Queue, Kernel, Buffer,
-    MemLen, SpatialDims, WorkDims, DeviceSpecifier};
+    MemLen, SpatialDims, WorkDims, DeviceSpecifier, KernelBuilder};
 
 static DIMS_ERR_MSG: &'static str = "This 'ProQue' has not had any dimensions specified. Use
     'ProQueBuilder::dims' during creation or 'ProQue::set_dims' after creation to specify.";
@@ -314,6 +314,20 @@ impl ProQue {
         }
     }
 
+    /// Returns a kernel builder with the program, queue, and global work
+    /// size (if dimensions have been set) pre-assigned.
+    ///
+    /// Call `::build` on the returned builder to create the kernel.
+    pub fn kernel_builder<S: Into<String>>(&self, name: S) -> KernelBuilder {
+        let mut kb = Kernel::builder();
+        kb.name(name).program(&self.program).queue(self.queue.clone());
+
+        if let Some(d) = self.dims {
+            kb.gws(d);
+        }
+        kb
+    }
+
     /// Returns a new buffer
     ///
     /// The default dimensions for this `ProQue` will be used when creating.
@@ -0,0 +1,184 @@
+//! Aggregation of command timings.
+
+use std;
+use std::time::Duration;
+use core::{EventInfo, EventInfoResult};
+use core::error::{Error as OclError, Result as OclResult};
+use standard::{Event, Kernel};
+
+
+/// Timing statistics for every command recorded under one tag.
+///
+/// Durations are execution times (from start to end) as reported by the
+/// device.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProfileStats {
+    /// The kernel name, command type, or other tag.
+    pub tag: String,
+    /// The number of commands.
+    pub count: usize,
+    /// The sum of all durations.
+    pub total: Duration,
+    /// The mean duration.
+    pub mean: Duration,
+    /// The shortest duration.
+    pub min: Duration,
+    /// The longest duration.
+    pub max: Duration,
+    /// The median duration.
+    pub p50: Duration,
+    /// The 90th percentile duration.
+    pub p90: Duration,
+    /// The 99th percentile duration.
+    pub p99: Duration,
+}
+
+impl ProfileStats {
+    /// Returns the statistics for `durations`, which must not be empty.
+    fn new(tag: String, mut durations: Vec<Duration>) -> ProfileStats {
+        assert!(!durations.is_empty());
+        durations.sort();
+
+        let total = durations.iter().fold(Duration::new(0, 0), |total, &d| total + d);
+
+        ProfileStats {
+            tag: tag,
+            count: durations.len(),
+            total: total,
+            mean: total / durations.len() as u32,
+            min: durations[0],
+            max: durations[durations.len() - 1],
+            p50: percentile(&durations, 50),
+            p90: percentile(&durations, 90),
+            p99: percentile(&durations, 99),
+        }
+    }
+}
+
+
+/// Collects events, tagged by kernel name or command type, and reports
+/// timing statistics for each tag.
+///
+/// Each event must come from a queue created with the
+/// `QUEUE_PROFILING_ENABLE` flag.
+///
+/// ```text
+/// let mut profiler = Profiler::new();
+///
+/// for _ in 0..100 {
+///     let mut event = Event::empty();
+///     kernel.cmd().enew(&mut event).enq()?;
+///     profiler.add_kernel(&kernel, event);
+/// }
+///
+/// println!("{}", profiler.table()?);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Profiler {
+    events: Vec<(String, Event)>,
+}
+
+impl Profiler {
+    /// Returns a new, empty, profiler.
+    pub fn new() -> Profiler {
+        Profiler { events: Vec::new() }
+    }
+
+    /// Records `event` under `tag`.
+    pub fn add<S: Into<String>>(&mut self, tag: S, event: Event) {
+        self.events.push((tag.into(), event));
+    }
+
+    /// Records `event` under the name of `kernel`.
+    pub fn add_kernel(&mut self, kernel: &Kernel, event: Event) {
+        self.add(kernel.name(), event);
+    }
+
+    /// Records `event` under its command type (such as `ReadBuffer` or
+    /// `NdRangeKernel`).
+    pub fn add_command(&mut self, event: Event) -> OclResult<()> {
+        let tag = match event.info(EventInfo::CommandType) {
+            EventInfoResult::CommandType(cmd_type) => format!("{:?}", cmd_type),
+            EventInfoResult::Error(err) => return Err(*err),
+            _ => return OclError::err("ocl::Profiler::add_command(): Unexpected \
+                'EventInfoResult' variant."),
+        };
+
+        self.add(tag, event);
+        Ok(())
+    }
+
+    /// Returns the number of events recorded.
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    /// Returns true if no events have been recorded.
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// Removes all recorded events.
+    pub fn clear(&mut self) {
+        self.events.clear();
+    }
+
+    /// Waits for each recorded event to complete and returns statistics for
+    /// each tag, in descending order of total time.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if any event fails or has no timings (see
+    /// `Event::timings`).
+    pub fn stats(&self) -> OclResult<Vec<ProfileStats>> {
+        // Tags in order of first appearance:
+        let mut tags: Vec<(&str, Vec<Duration>)> = Vec::new();
+
+        for &(ref tag, ref event) in self.events.iter() {
+            try!(event.wait());
+            let duration = try!(event.timings()).duration();
+
+            match tags.iter().position(|&(t, _)| t == tag.as_str()) {
+                Some(idx) => tags[idx].1.push(duration),
+                None => tags.push((tag.as_str(), vec![duration])),
+            }
+        }
+
+        let mut stats: Vec<ProfileStats> = tags.into_iter()
+            .map(|(tag, durations)| ProfileStats::new(tag.to_owned(), durations))
+            .collect();
+
+        stats.sort_by(|a, b| b.total.cmp(&a.total));
+        Ok(stats)
+    }
+
+    /// Returns the statistics for each tag (see `::stats`) formatted as a
+    /// table with times in microseconds.
+    pub fn table(&self) -> OclResult<String> {
+        let stats = try!(self.stats());
+        let tag_width = stats.iter().map(|s| s.tag.len()).fold(3, std::cmp::max);
+
+        let mut table = format!("{:<w$} {:>7} {:>12} {:>10} {:>10} {:>10} {:>10} {:>10} {:>10}\n",
+            "Tag", "Count", "Total (us)", "Mean", "Min", "Max", "p50", "p90", "p99", w = tag_width);
+
+        for s in stats.iter() {
+            table.push_str(&format!("{:<w$} {:>7} {:>12.3} {:>10.3} {:>10.3} {:>10.3} {:>10.3} \
+                {:>10.3} {:>10.3}\n", s.tag, s.count, micros(s.total), micros(s.mean),
+                micros(s.min), micros(s.max), micros(s.p50), micros(s.p90), micros(s.p99),
+                w = tag_width));
+        }
+
+        Ok(table)
+    }
+}
+
+
+/// Returns the nearest-rank percentile, `pct`, of `sorted`.
+fn percentile(sorted: &[Duration], pct: usize) -> Duration {
+    let rank = (pct * sorted.len() + 99) / 100;
+    sorted[std::cmp::max(rank, 1) - 1]
+}
+
+fn micros(duration: Duration) -> f64 {
+    duration.as_secs() as f64 * 1_000_000.0 + duration.subsec_nanos() as f64 / 1_000.0
+}
@@ -2,10 +2,13 @@
 
 use std;
 use std::ops::{Deref, DerefMut};
+use std::sync::Arc;
 use core::error::{Result as OclResult};
-use core::{self, CommandQueue as CommandQueueCore, Context as ContextCore,
-    CommandQueueInfo, CommandQueueInfoResult, OpenclVersion, CommandQueueProperties};
-use standard::{Context, Device};
+use core::{self, OclPrm, CommandQueue as CommandQueueCore, Context as ContextCore,
+    CommandQueueInfo, CommandQueueInfoResult, OpenclVersion, CommandQueueProperties, ClWaitList,
+    ClEventPtrNew};
+use standard::{Context, Device, Buffer, ResourceTracker, Timeline};
+use standard::host_fn;
 
 /// A command queue which manages all actions taken on kernels, buffers, and
 /// images.
@@ -24,6 +27,7 @@ pub struct Queue {
     context_obj_core: ContextCore,
     device: Device,
     device_version: OpenclVersion,
+    tracker: Arc<ResourceTracker>,
 }
 
 impl Queue {
@@ -38,12 +42,115 @@ impl Queue {
             context_obj_core: context.core_as_ref().clone(),
             device: device,
             device_version: device_version,
+            tracker: Arc::new(ResourceTracker::new()),
+        })
+    }
+
+    /// Returns a new queue on the same context and device as this one,
+    /// created with `properties`.
+    ///
+    /// The new queue has its own `ResourceTracker` (see `::tracker`).
+    pub fn with_properties(&self, properties: Option<CommandQueueProperties>)
+            -> OclResult<Queue> {
+        let obj_core = try!(core::create_command_queue(&self.context_obj_core, &self.device,
+            properties));
+
+        Ok(Queue {
+            obj_core: obj_core,
+            context_obj_core: self.context_obj_core.clone(),
+            device: self.device.clone(),
+            device_version: self.device_version,
+            tracker: Arc::new(ResourceTracker::new()),
         })
     }
 
     /// Blocks until all commands in this queue have completed before returning.
+    ///
+    /// Objects retained on behalf of completed commands are then released
+    /// (see `ResourceTracker`). The time spent is recorded as a host span on
+    /// the attached timeline, if any.
     pub fn finish(&self) {
-        core::finish(&self.obj_core).unwrap();
+        match self.tracker.timeline() {
+            Some(timeline) => timeline.host_span("Queue::finish",
+                || core::finish(&self.obj_core)).unwrap(),
+            None => core::finish(&self.obj_core).unwrap(),
+        }
+        self.tracker.release_completed();
+    }
+
+    /// Attaches `timeline` to this queue (and its clones), recording each
+    /// command subsequently enqueued, or detaches the current timeline if
+    /// `None`.
+    ///
+    /// See `Timeline`.
+    pub fn set_timeline(&self, timeline: Option<&Timeline>) {
+        self.tracker.set_timeline(timeline.cloned());
+    }
+
+    /// Returns the tracker which retains the objects used by commands
+    /// enqueued on this queue (and its clones) until they complete.
+    pub fn tracker(&self) -> &ResourceTracker {
+        &self.tracker
+    }
+
+    /// Enqueues a host function (closure) which runs, in queue order, once
+    /// the events in `ewait` have completed.
+    ///
+    /// Commands enqueued afterwards (on an in-order queue) wait for `f` to
+    /// return. `enew` is completed when it does.
+    ///
+    /// Uses `clEnqueueNativeKernel` if the device has the
+    /// `EXEC_NATIVE_KERNEL` capability (such as most CPU devices). Otherwise
+    /// `f` is called from an event callback on a runtime thread, with a user
+    /// event blocking subsequent commands until it returns. Either way `f`
+    /// must not block on commands enqueued after it.
+    ///
+    /// A panic within `f` is caught and ends the function early.
+    ///
+    /// If an event in `ewait` terminates abnormally, `f` is never called.
+    /// With native kernels, `f` (along with anything it has captured) is
+    /// then leaked as the runtime gives no notice that it will not run.
+    /// Otherwise it is dropped.
+    ///
+    /// ## Errors
+    ///
+    /// On error, `f` is dropped without being called and any event already
+    /// associated with `enew` should be disregarded.
+    pub fn enqueue_host_fn<F>(&self, f: F, ewait: Option<&ClWaitList>,
+            enew: Option<&mut ClEventPtrNew>) -> OclResult<()>
+            where F: FnOnce() + Send + 'static
+    {
+        host_fn::enqueue(self, &[], host_fn::host_fn(move |_| f()), ewait, enew)
+    }
+
+    /// Enqueues a host function (closure) which, like `::enqueue_host_fn`,
+    /// runs in queue order and is passed the contents of each of `buffers`
+    /// as a host-accessible slice.
+    ///
+    /// With native kernels, the runtime provides the slices directly.
+    /// Otherwise, each buffer is mapped before and unmapped after `f` runs.
+    /// The buffers are retained until `f` has run.
+    ///
+    /// If an event in `ewait` terminates abnormally, `f` is not called (see
+    /// `::enqueue_host_fn`) but the buffers are still unmapped and `enew`
+    /// terminates abnormally as well.
+    pub fn enqueue_host_fn_mem<T, F>(&self, buffers: &[&Buffer<T>], f: F,
+            ewait: Option<&ClWaitList>, enew: Option<&mut ClEventPtrNew>) -> OclResult<()>
+            where T: OclPrm + 'static, F: FnOnce(&mut [&mut [T]]) + Send + 'static
+    {
+        let mems: Vec<_> = buffers.iter()
+            .map(|buf| (buf.core_as_ref(), buf.len() * std::mem::size_of::<T>()))
+            .collect();
+        let lens: Vec<usize> = buffers.iter().map(|buf| buf.len()).collect();
+        let retained: Vec<_> = buffers.iter().map(|buf| buf.core_as_ref().clone()).collect();
+
+        host_fn::enqueue(self, &mems, host_fn::host_fn(move |host_ptrs| {
+            let _retained = retained;
+            let mut slices: Vec<&mut [T]> = host_ptrs.iter().zip(lens.iter())
+                .map(|(&ptr, &len)| unsafe { std::slice::from_raw_parts_mut(ptr as *mut T, len) })
+                .collect();
+            f(&mut slices)
+        }), ewait, enew)
     }
 
     /// Returns a reference to the core pointer wrapper, usable by functions in
@@ -0,0 +1,318 @@
+//! Host-side reference implementations of device behavior.
+//!
+//! Useful as a 'golden model' when verifying the results of kernels.
+
+use core::error::{Error as OclError, Result as OclResult};
+use core::{OclPrm, AddressingMode, FilterMode};
+use standard::SpatialDims;
+
+/// The number of components in each pixel of host data passed to a
+/// `HostSampler` (always four: RGBA).
+const PIXEL_COMPONENTS: usize = 4;
+
+/// A texel coordinate along one dimension, either inside the image or
+/// referring to the border color.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Texel {
+    Idx(usize),
+    Border,
+}
+
+/// Rounds to the nearest integer, rounding halfway cases to even (the
+/// behavior of the OpenCL C `rint` function).
+pub fn rint(x: f32) -> f32 {
+    let r = x.round();
+    if (x - x.trunc()).abs() == 0.5 {
+        (x / 2.0).round() * 2.0
+    } else {
+        r
+    }
+}
+
+/// Returns the fractional part of `x` (`x - floor(x)`).
+fn frac(x: f32) -> f32 {
+    x - x.floor()
+}
+
+/// Resolves a signed texel index into a `Texel`, treating anything outside
+/// of `[0, size)` as the border.
+fn texel(i: i64, size: usize) -> Texel {
+    if i < 0 || i >= size as i64 { Texel::Border } else { Texel::Idx(i as usize) }
+}
+
+fn clamp(i: i64, min: i64, max: i64) -> i64 {
+    if i < min { min } else if i > max { max } else { i }
+}
+
+
+/// A host-side model of an OpenCL image sampler.
+///
+/// Reproduces the addressing modes (`ClampToEdge`, `Clamp`, `Repeat`,
+/// `MirroredRepeat`, and `None`) and filter modes (`Nearest`, `Linear`) as
+/// specified in section 8.2 of the [OpenCL 1.2 specification] so that the
+/// results of kernels using `read_image{f|i|ui}` can be compared against a
+/// known-good result computed on the host.
+///
+/// ## Host Data Layout
+///
+/// Pixel data is always four components (RGBA) per pixel, stored row-major:
+/// the component `c` of the pixel at `(x, y, z)` is found at index
+/// `((z * height + y) * width + x) * 4 + c`. Data for images with other
+/// channel orders must be expanded to RGBA as described in section 8.3 of
+/// the specification before sampling.
+///
+/// Because all pixels have an alpha component, the border color used by
+/// `AddressingMode::Clamp` is `(0, 0, 0, 0)`.
+///
+/// ## Precision
+///
+/// Devices are permitted to use reduced precision when computing linear
+/// filter weights (commonly 8 bits of fraction on GPUs). Results using
+/// `FilterMode::Linear` should therefore be compared with a tolerance.
+///
+/// [OpenCL 1.2 specification]: https://www.khronos.org/registry/cl/specs/opencl-1.2.pdf
+///
+#[derive(Clone, Copy, Debug)]
+pub struct HostSampler {
+    normalize_coords: bool,
+    addressing_mode: AddressingMode,
+    filter_mode: FilterMode,
+}
+
+impl HostSampler {
+    /// Returns a new host sampler with the same settings a device sampler
+    /// created with `Sampler::new` would have.
+    pub fn new(normalize_coords: bool, addressing_mode: AddressingMode, filter_mode: FilterMode)
+            -> HostSampler
+    {
+        HostSampler {
+            normalize_coords: normalize_coords,
+            addressing_mode: addressing_mode,
+            filter_mode: filter_mode,
+        }
+    }
+
+    /// Returns whether or not coordinates are normalized.
+    pub fn normalize_coords(&self) -> bool {
+        self.normalize_coords
+    }
+
+    /// Returns the addressing mode.
+    pub fn addressing_mode(&self) -> AddressingMode {
+        self.addressing_mode
+    }
+
+    /// Returns the filter mode.
+    pub fn filter_mode(&self) -> FilterMode {
+        self.filter_mode
+    }
+
+    /// Samples float pixel data at `coord` and returns exactly what
+    /// `read_imagef` would return on a conforming device.
+    ///
+    /// `dims` are the dimensions of the image (one, two, or three). Only as
+    /// many components of `coord` as there are dimensions are used.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if `pixels` does not contain exactly four components
+    /// for each pixel within `dims`, if `Repeat` or `MirroredRepeat`
+    /// addressing is used with unnormalized coordinates, or if `coord` falls
+    /// outside of the image while using `AddressingMode::None` (the result
+    /// of which is undefined).
+    ///
+    pub fn sample<D: Into<SpatialDims>>(&self, pixels: &[f32], dims: D, coord: [f32; 3])
+            -> OclResult<[f32; 4]>
+    {
+        let dims = dims.into();
+        let image_dims = try!(self.check(pixels.len(), &dims));
+
+        match self.filter_mode {
+            FilterMode::Nearest => self.sample_nearest(pixels, &dims, image_dims, coord),
+            FilterMode::Linear => self.sample_linear(pixels, &dims, image_dims, coord),
+        }
+    }
+
+    /// Samples integer pixel data at `coord` and returns exactly what
+    /// `read_imagei` or `read_imageui` would return on a conforming device.
+    ///
+    /// See `::sample` for details about `dims` and `coord`.
+    ///
+    /// ## Errors
+    ///
+    /// Integer images may only be sampled using `FilterMode::Nearest`. See
+    /// `::sample` for other errors.
+    ///
+    pub fn sample_int<T: OclPrm, D: Into<SpatialDims>>(&self, pixels: &[T], dims: D,
+            coord: [f32; 3]) -> OclResult<[T; 4]>
+    {
+        if let FilterMode::Linear = self.filter_mode {
+            return OclError::err("ocl::HostSampler::sample_int: Integer images may only be \
+                sampled using 'FilterMode::Nearest'.");
+        }
+
+        let dims = dims.into();
+        let image_dims = try!(self.check(pixels.len(), &dims));
+        self.sample_nearest(pixels, &dims, image_dims, coord)
+    }
+
+    /// Verifies the data length and settings and returns the image
+    /// dimensions as an array.
+    fn check(&self, data_len: usize, dims: &SpatialDims) -> OclResult<[usize; 3]> {
+        let image_dims = try!(dims.to_lens());
+
+        if data_len != dims.to_len() * PIXEL_COMPONENTS {
+            return OclError::err(format!("ocl::HostSampler: Pixel data length ({}) must be \
+                exactly four components per pixel ({} pixels * 4 = {}).", data_len,
+                dims.to_len(), dims.to_len() * PIXEL_COMPONENTS));
+        }
+
+        match self.addressing_mode {
+            AddressingMode::Repeat | AddressingMode::MirroredRepeat if !self.normalize_coords => {
+                OclError::err(format!("ocl::HostSampler: 'AddressingMode::{:?}' may only be used \
+                    with normalized coordinates.", self.addressing_mode))
+            },
+            _ => Ok(image_dims),
+        }
+    }
+
+    /// Returns the texel selected along one dimension by nearest filtering.
+    fn nearest_texel(&self, s: f32, size: usize) -> OclResult<Texel> {
+        let w = size as f32;
+
+        match self.addressing_mode {
+            AddressingMode::Repeat => {
+                let u = (s - s.floor()) * w;
+                let mut i = u.floor() as i64;
+                if i > size as i64 - 1 { i -= size as i64; }
+                Ok(texel(i, size))
+            },
+            AddressingMode::MirroredRepeat => {
+                let s = (s - 2.0 * rint(0.5 * s)).abs();
+                let u = s * w;
+                let i = u.floor() as i64;
+                Ok(texel(clamp(i, 0, size as i64 - 1), size))
+            },
+            addressing_mode => {
+                let u = if self.normalize_coords { s * w } else { s };
+                let i = u.floor() as i64;
+
+                match addressing_mode {
+                    AddressingMode::ClampToEdge => Ok(texel(clamp(i, 0, size as i64 - 1), size)),
+                    AddressingMode::Clamp => Ok(texel(i, size)),
+                    _ => match texel(i, size) {
+                        Texel::Border => self.err_undefined(s),
+                        t => Ok(t),
+                    },
+                }
+            },
+        }
+    }
+
+    /// Returns the two texels selected along one dimension by linear
+    /// filtering along with the weight (`a`, `b`, or `c` in the spec) of the
+    /// second.
+    fn linear_texels(&self, s: f32, size: usize) -> OclResult<(Texel, Texel, f32)> {
+        let w = size as f32;
+        let max = size as i64 - 1;
+
+        match self.addressing_mode {
+            AddressingMode::Repeat => {
+                let u = (s - s.floor()) * w;
+                let mut i0 = (u - 0.5).floor() as i64;
+                let mut i1 = i0 + 1;
+                if i0 < 0 { i0 += size as i64; }
+                if i1 > max { i1 -= size as i64; }
+                Ok((texel(i0, size), texel(i1, size), frac(u - 0.5)))
+            },
+            AddressingMode::MirroredRepeat => {
+                let s = (s - 2.0 * rint(0.5 * s)).abs();
+                let u = s * w;
+                let i0 = (u - 0.5).floor() as i64;
+                let i1 = i0 + 1;
+                Ok((texel(clamp(i0, 0, max), size), texel(clamp(i1, 0, max), size),
+                    frac(u - 0.5)))
+            },
+            addressing_mode => {
+                let u = if self.normalize_coords { s * w } else { s };
+                let i0 = (u - 0.5).floor() as i64;
+                let i1 = i0 + 1;
+                let a = frac(u - 0.5);
+
+                match addressing_mode {
+                    AddressingMode::ClampToEdge => Ok((texel(clamp(i0, 0, max), size),
+                        texel(clamp(i1, 0, max), size), a)),
+                    AddressingMode::Clamp => Ok((texel(i0, size), texel(i1, size), a)),
+                    _ => match (texel(i0, size), texel(i1, size)) {
+                        (Texel::Border, _) | (_, Texel::Border) => self.err_undefined(s),
+                        (t0, t1) => Ok((t0, t1, a)),
+                    },
+                }
+            },
+        }
+    }
+
+    fn sample_nearest<T: OclPrm>(&self, pixels: &[T], dims: &SpatialDims, image_dims: [usize; 3],
+            coord: [f32; 3]) -> OclResult<[T; 4]>
+    {
+        let mut texels = [Texel::Idx(0); 3];
+        for d in 0..dims.dim_count() as usize {
+            texels[d] = try!(self.nearest_texel(coord[d], image_dims[d]));
+        }
+        Ok(fetch(pixels, image_dims, texels))
+    }
+
+    fn sample_linear(&self, pixels: &[f32], dims: &SpatialDims, image_dims: [usize; 3],
+            coord: [f32; 3]) -> OclResult<[f32; 4]>
+    {
+        // Texel pairs and the weight of the second texel of each pair. Unused
+        // dimensions always resolve to the first texel.
+        let mut pairs = [(Texel::Idx(0), Texel::Idx(0), 0.0f32); 3];
+        for d in 0..dims.dim_count() as usize {
+            pairs[d] = try!(self.linear_texels(coord[d], image_dims[d]));
+        }
+
+        let mut result = [0.0f32; 4];
+        for corner in 0..8 {
+            let mut texels = [Texel::Idx(0); 3];
+            let mut weight = 1.0f32;
+
+            for d in 0..3 {
+                let (t0, t1, a) = pairs[d];
+                if corner & (1 << d) == 0 {
+                    texels[d] = t0;
+                    weight *= 1.0 - a;
+                } else {
+                    texels[d] = t1;
+                    weight *= a;
+                }
+            }
+
+            if weight != 0.0 {
+                let pixel = fetch(pixels, image_dims, texels);
+                for c in 0..PIXEL_COMPONENTS {
+                    result[c] += weight * pixel[c];
+                }
+            }
+        }
+
+        Ok(result)
+    }
+
+    fn err_undefined<T>(&self, s: f32) -> OclResult<T> {
+        OclError::err(format!("ocl::HostSampler: Coordinate ({}) is outside of the image. \
+            The result of sampling outside of an image using 'AddressingMode::None' is \
+            undefined.", s))
+    }
+}
+
+/// Returns the pixel at `texels` or the border color.
+fn fetch<T: OclPrm>(pixels: &[T], image_dims: [usize; 3], texels: [Texel; 3]) -> [T; 4] {
+    match (texels[0], texels[1], texels[2]) {
+        (Texel::Idx(x), Texel::Idx(y), Texel::Idx(z)) => {
+            let idx = ((z * image_dims[1] + y) * image_dims[0] + x) * PIXEL_COMPONENTS;
+            [pixels[idx], pixels[idx + 1], pixels[idx + 2], pixels[idx + 3]]
+        },
+        _ => [Default::default(); 4],
+    }
+}
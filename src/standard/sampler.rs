@@ -2,14 +2,258 @@
 
 use std;
 use std::ops::{Deref, DerefMut};
-use core::error::{Result as OclResult};
-use core::{self, Sampler as SamplerCore, AddressingMode, FilterMode, SamplerInfo, SamplerInfoResult};
-use standard::Context;
+use core::error::{Error as OclError, Result as OclResult};
+use core::{self, OclPrm, Sampler as SamplerCore, AddressingMode, FilterMode, SamplerInfo,
+    SamplerInfoResult, KernelArg};
+use standard::{Context, SpatialDims, HostSampler, Kernel, KernelArgValue};
+
+#[cfg(feature = "opencl_version_2_0")]
+#[allow(non_camel_case_types)]
+mod ffi_2_0 {
+    //! OpenCL 2.0 sampler functions and constants not yet available in
+    //! `cl-sys`.
+
+    use ffi::{cl_context, cl_sampler, cl_int, cl_bitfield};
+
+    pub type cl_sampler_properties = cl_bitfield;
+
+    pub const CL_SAMPLER_MIP_FILTER_MODE: cl_sampler_properties = 0x1155;
+    pub const CL_SAMPLER_LOD_MIN: cl_sampler_properties = 0x1156;
+    pub const CL_SAMPLER_LOD_MAX: cl_sampler_properties = 0x1157;
+
+    #[cfg_attr(target_os = "macos", link(name = "OpenCL", kind = "framework"))]
+    #[cfg_attr(not(target_os = "macos"), link(name = "OpenCL"))]
+    extern "system" {
+        pub fn clCreateSamplerWithProperties(context: cl_context,
+            sampler_properties: *const cl_sampler_properties,
+            errcode_ret: *mut cl_int) -> cl_sampler;
+    }
+}
+
+
+/// The settings a `Sampler` was created with.
+///
+/// Captured when the sampler is created and available from
+/// `Sampler::properties` without querying the device.
+///
+/// `mip_filter_mode`, `lod_min`, and `lod_max` are only ever `Some` when the
+/// sampler was created on an OpenCL 2.0+ context using
+/// `clCreateSamplerWithProperties` (see `SamplerBuilder`).
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SamplerProperties {
+    pub normalize_coords: bool,
+    pub addressing_mode: AddressingMode,
+    pub filter_mode: FilterMode,
+    pub mip_filter_mode: Option<FilterMode>,
+    pub lod_min: Option<f32>,
+    pub lod_max: Option<f32>,
+}
+
+
+/// A builder for `Sampler`.
+///
+/// Able to set the mipmap filter mode and level of detail range on OpenCL
+/// 2.0+ devices supporting the `cl_khr_mipmap_image` extension. These
+/// settings require the `opencl_version_2_0` crate feature.
+///
+pub struct SamplerBuilder {
+    properties: SamplerProperties,
+}
+
+impl SamplerBuilder {
+    /// Returns a new `SamplerBuilder` with the same defaults as
+    /// `Sampler::with_defaults`.
+    ///
+    /// ## Defaults
+    ///
+    /// - `normalize_coords`: false
+    /// - `addressing_mode`: `AddressingMode::None`
+    /// - `filter_mode`: `FilterMode::Nearest`
+    /// - `mip_filter_mode`, `lod_min`, `lod_max`: unset (device defaults)
+    ///
+    pub fn new() -> SamplerBuilder {
+        SamplerBuilder {
+            properties: SamplerProperties {
+                normalize_coords: false,
+                addressing_mode: AddressingMode::None,
+                filter_mode: FilterMode::Nearest,
+                mip_filter_mode: None,
+                lod_min: None,
+                lod_max: None,
+            },
+        }
+    }
+
+    /// Specifies whether or not image coordinates are normalized.
+    pub fn normalize_coords(&mut self, normalize_coords: bool) -> &mut SamplerBuilder {
+        self.properties.normalize_coords = normalize_coords;
+        self
+    }
+
+    /// Specifies how out-of-range image coordinates are handled.
+    pub fn addressing_mode(&mut self, addressing_mode: AddressingMode) -> &mut SamplerBuilder {
+        self.properties.addressing_mode = addressing_mode;
+        self
+    }
+
+    /// Specifies the filter applied when reading an image.
+    pub fn filter_mode(&mut self, filter_mode: FilterMode) -> &mut SamplerBuilder {
+        self.properties.filter_mode = filter_mode;
+        self
+    }
+
+    /// Specifies the filter applied between mipmap levels.
+    ///
+    /// Requires OpenCL 2.0+, the `cl_khr_mipmap_image` extension, and the
+    /// `opencl_version_2_0` crate feature.
+    pub fn mip_filter_mode(&mut self, mip_filter_mode: FilterMode) -> &mut SamplerBuilder {
+        self.properties.mip_filter_mode = Some(mip_filter_mode);
+        self
+    }
+
+    /// Specifies the minimum level of detail used when sampling mipmaps.
+    ///
+    /// Requires OpenCL 2.0+, the `cl_khr_mipmap_image` extension, and the
+    /// `opencl_version_2_0` crate feature.
+    pub fn lod_min(&mut self, lod_min: f32) -> &mut SamplerBuilder {
+        self.properties.lod_min = Some(lod_min);
+        self
+    }
+
+    /// Specifies the maximum level of detail used when sampling mipmaps.
+    ///
+    /// Requires OpenCL 2.0+, the `cl_khr_mipmap_image` extension, and the
+    /// `opencl_version_2_0` crate feature.
+    pub fn lod_max(&mut self, lod_max: f32) -> &mut SamplerBuilder {
+        self.properties.lod_max = Some(lod_max);
+        self
+    }
+
+    /// Builds and returns a new `Sampler`.
+    ///
+    /// Uses `clCreateSamplerWithProperties` when every device in `context`
+    /// supports OpenCL 2.0+ (and the `opencl_version_2_0` feature is
+    /// enabled), otherwise falls back to `clCreateSampler`.
+    ///
+    /// ## Errors
+    ///
+    /// Setting a mip filter mode or level of detail range when
+    /// `clCreateSamplerWithProperties` is unavailable is an error.
+    ///
+    pub fn build(&self, context: &Context) -> OclResult<Sampler> {
+        let props = self.properties;
+        let has_2_0_props = props.mip_filter_mode.is_some() || props.lod_min.is_some() ||
+            props.lod_max.is_some();
+
+        if try!(supports_properties(context)) {
+            let sampler_core = try!(create_sampler_with_properties(context, &props));
+            return Ok(Sampler { obj_core: sampler_core, properties: props });
+        }
+
+        if has_2_0_props {
+            return OclError::err("ocl::SamplerBuilder::build(): The mip filter mode and level \
+                of detail range can only be set on OpenCL 2.0+ contexts with the \
+                'opencl_version_2_0' feature enabled.");
+        }
+
+        let sampler_core = try!(core::create_sampler(context, props.normalize_coords,
+            props.addressing_mode, props.filter_mode));
+
+        Ok(Sampler { obj_core: sampler_core, properties: props })
+    }
+}
+
+
+/// Returns true if `clCreateSamplerWithProperties` can be used with every
+/// device in `context`.
+#[cfg(feature = "opencl_version_2_0")]
+fn supports_properties(context: &Context) -> OclResult<bool> {
+    use core::OpenclVersion;
+
+    for device in context.devices() {
+        if try!(device.version()) < OpenclVersion::new(2, 0) {
+            return Ok(false);
+        }
+    }
+    Ok(!context.devices().is_empty())
+}
+
+#[cfg(not(feature = "opencl_version_2_0"))]
+fn supports_properties(_: &Context) -> OclResult<bool> {
+    Ok(false)
+}
+
+#[cfg(feature = "opencl_version_2_0")]
+fn create_sampler_with_properties(context: &Context, props: &SamplerProperties)
+        -> OclResult<SamplerCore>
+{
+    use std::mem;
+    use ffi::{self, cl_sampler};
+    use self::ffi_2_0::{cl_sampler_properties, CL_SAMPLER_MIP_FILTER_MODE, CL_SAMPLER_LOD_MIN,
+        CL_SAMPLER_LOD_MAX};
+
+    // Float property values are passed in the low bits of the 64 bit value.
+    let lod_bits = |lod: f32| unsafe { mem::transmute::<f32, u32>(lod) } as cl_sampler_properties;
+
+    let mut prop_list: Vec<cl_sampler_properties> = vec![
+        SamplerInfo::NormalizedCoords as cl_sampler_properties,
+            props.normalize_coords as cl_sampler_properties,
+        SamplerInfo::AddressingMode as cl_sampler_properties,
+            props.addressing_mode as cl_sampler_properties,
+        SamplerInfo::FilterMode as cl_sampler_properties,
+            props.filter_mode as cl_sampler_properties,
+    ];
+
+    if let Some(mfm) = props.mip_filter_mode {
+        prop_list.extend_from_slice(&[CL_SAMPLER_MIP_FILTER_MODE, mfm as cl_sampler_properties]);
+    }
+    if let Some(lod_min) = props.lod_min {
+        prop_list.extend_from_slice(&[CL_SAMPLER_LOD_MIN, lod_bits(lod_min)]);
+    }
+    if let Some(lod_max) = props.lod_max {
+        prop_list.extend_from_slice(&[CL_SAMPLER_LOD_MAX, lod_bits(lod_max)]);
+    }
+    prop_list.push(0);
+
+    let mut errcode: ffi::cl_int = 0;
+
+    let sampler_ptr: cl_sampler = unsafe { ffi_2_0::clCreateSamplerWithProperties(
+        context.core_as_ref().as_ptr(),
+        prop_list.as_ptr(),
+        &mut errcode,
+    ) };
+
+    if errcode != ffi::CL_SUCCESS || sampler_ptr.is_null() {
+        return OclError::err(format!("ocl::SamplerBuilder::build(): \
+            'clCreateSamplerWithProperties' failed with error code: {}.", errcode));
+    }
+
+    unsafe { Ok(SamplerCore::from_fresh_ptr(sampler_ptr)) }
+}
+
+#[cfg(not(feature = "opencl_version_2_0"))]
+fn create_sampler_with_properties(_: &Context, _: &SamplerProperties) -> OclResult<SamplerCore> {
+    OclError::err("ocl::SamplerBuilder::build(): 'clCreateSamplerWithProperties' is unavailable \
+        without the 'opencl_version_2_0' feature.")
+}
+
 
 /// An image sampler used to process images.
-pub struct Sampler(SamplerCore);
+pub struct Sampler {
+    obj_core: SamplerCore,
+    properties: SamplerProperties,
+}
 
 impl Sampler {
+    /// Returns a new `SamplerBuilder`.
+    ///
+    /// Use this to set the mip filter mode or level of detail range on
+    /// OpenCL 2.0+ devices.
+    pub fn builder() -> SamplerBuilder {
+        SamplerBuilder::new()
+    }
+
     /// Creates and returns a new sampler.
     ///
     /// ## Enum Quick Reference
@@ -33,10 +277,11 @@ impl Sampler {
     pub fn new(context: &Context, normalize_coords: bool, addressing_mode: AddressingMode,
             filter_mode: FilterMode) -> OclResult<Sampler>
     {
-        let sampler_core = try!(core::create_sampler(context, normalize_coords,
-            addressing_mode, filter_mode));
-
-        Ok(Sampler(sampler_core))
+        SamplerBuilder::new()
+            .normalize_coords(normalize_coords)
+            .addressing_mode(addressing_mode)
+            .filter_mode(filter_mode)
+            .build(context)
     }
 
     /// Creates and returns a new sampler with some default settings.
@@ -49,19 +294,49 @@ impl Sampler {
     ///
     pub fn with_defaults(context: &Context) -> OclResult<Sampler>
     {
-        let sampler_core = try!(core::create_sampler(context, false,
-            AddressingMode::None, FilterMode::Nearest));
+        SamplerBuilder::new().build(context)
+    }
 
-        Ok(Sampler(sampler_core))
+    /// Returns the settings this sampler was created with.
+    pub fn properties(&self) -> &SamplerProperties {
+        &self.properties
     }
 
     /// Returns various kinds of information about the sampler.
+    ///
+    /// Use `::properties` for the settings this sampler was created with.
     pub fn info(&self, info_kind: SamplerInfo) -> SamplerInfoResult {
-        // match core::get_sampler_info(&self.0, info_kind) {
+        // match core::get_sampler_info(&self.obj_core, info_kind) {
         //     Ok(res) => res,
         //     Err(err) => SamplerInfoResult::Error(Box::new(err)),
         // }
-        core::get_sampler_info(&self.0, info_kind)
+        core::get_sampler_info(&self.obj_core, info_kind)
+    }
+
+    /// Returns a host-side model of this sampler with the same settings.
+    ///
+    /// See `ocl::reference::HostSampler` for more information.
+    pub fn to_host_sampler(&self) -> HostSampler {
+        HostSampler::new(self.properties.normalize_coords, self.properties.addressing_mode,
+            self.properties.filter_mode)
+    }
+
+    /// Samples float pixel data on the host at `coord`, returning exactly
+    /// what `read_imagef` using this sampler would return on a conforming
+    /// device.
+    ///
+    /// `pixels` must contain four components (RGBA) per pixel. See
+    /// `ocl::reference::HostSampler::sample` for details.
+    pub fn sample_host<D: Into<SpatialDims>>(&self, pixels: &[f32], dims: D, coord: [f32; 3])
+            -> OclResult<[f32; 4]>
+    {
+        self.to_host_sampler().sample(pixels, dims, coord)
+    }
+
+    /// Returns a reference to the core pointer wrapper, usable by functions in
+    /// the `core` module.
+    pub fn core_as_ref(&self) -> &SamplerCore {
+        &self.obj_core
     }
 
     fn fmt_info(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
@@ -71,6 +346,9 @@ impl Sampler {
             .field("NormalizedCoords", &self.info(SamplerInfo::NormalizedCoords))
             .field("AddressingMode", &self.info(SamplerInfo::AddressingMode))
             .field("FilterMode", &self.info(SamplerInfo::FilterMode))
+            .field("MipFilterMode", &self.properties.mip_filter_mode)
+            .field("LodMin", &self.properties.lod_min)
+            .field("LodMax", &self.properties.lod_max)
             .finish()
     }
 }
@@ -85,12 +363,24 @@ impl Deref for Sampler {
     type Target = SamplerCore;
 
     fn deref(&self) -> &SamplerCore {
-        &self.0
+        &self.obj_core
     }
 }
 
 impl DerefMut for Sampler {
     fn deref_mut(&mut self) -> &mut SamplerCore {
-        &mut self.0
+        &mut self.obj_core
+    }
+}
+
+impl<'a, T: OclPrm> From<&'a Sampler> for KernelArg<'a, T> {
+    fn from(sampler: &'a Sampler) -> KernelArg<'a, T> {
+        KernelArg::Sampler(&sampler.obj_core)
+    }
+}
+
+impl<'a> KernelArgValue for &'a Sampler {
+    fn set_kernel_arg(&self, kernel: &mut Kernel, arg_idx: u32) -> OclResult<()> {
+        kernel.set_arg_smp(arg_idx, self)
     }
 }
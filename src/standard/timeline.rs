@@ -0,0 +1,280 @@
+//! Recording of command timelines for export as Chrome trace event JSON.
+
+use std;
+use std::fmt::Write as FmtWrite;
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use core::{self, ClWaitList, EventInfo, EventInfoResult, DeviceInfo, DeviceInfoResult};
+use core::error::{Error as OclError, Result as OclResult};
+use standard::Event;
+use standard::work_group::queue_device;
+
+
+/// An enqueued command.
+#[derive(Clone, Debug)]
+struct CommandRecord {
+    event: Event,
+    kernel_name: Option<String>,
+    enqueued_at: Duration,
+}
+
+/// A span of time spent on the host (such as waiting for commands).
+#[derive(Clone, Debug)]
+struct HostSpan {
+    name: String,
+    start: Duration,
+    end: Duration,
+}
+
+#[derive(Debug)]
+struct Records {
+    commands: Vec<CommandRecord>,
+    host_spans: Vec<HostSpan>,
+}
+
+
+/// Records the commands enqueued on one or more queues, along with host-side
+/// waits, for export as Chrome trace event JSON (viewable in
+/// `chrome://tracing` or Perfetto).
+///
+/// Attach a timeline to a queue (and its clones) with `Queue::set_timeline`.
+/// Each command enqueued afterwards is recorded with its command type, kernel
+/// name, and queue. Each queue (labeled with its device) is shown on its own
+/// lane, using the profiling start and end times of its commands, and host
+/// spans on another. Only commands enqueued on queues created with the
+/// `QUEUE_PROFILING_ENABLE` flag appear in the trace.
+///
+/// Device times are aligned with the host clock using the time at which each
+/// command was enqueued.
+///
+/// Recorded events are retained until the timeline is cleared or dropped.
+/// Clones share the same records.
+#[derive(Clone, Debug)]
+pub struct Timeline {
+    records: Arc<Mutex<Records>>,
+    start: Instant,
+}
+
+impl Timeline {
+    /// Returns a new, empty, timeline.
+    pub fn new() -> Timeline {
+        Timeline {
+            records: Arc::new(Mutex::new(Records { commands: Vec::new(), host_spans: Vec::new() })),
+            start: Instant::now(),
+        }
+    }
+
+    /// Records the command associated with `event`, enqueued (on the host)
+    /// at `enqueued_at`.
+    ///
+    /// Called automatically for commands enqueued on a queue to which this
+    /// timeline is attached.
+    pub fn record(&self, event: &Event, kernel_name: Option<String>, enqueued_at: Instant) {
+        if event.is_empty() { return; }
+
+        self.records.lock().unwrap().commands.push(CommandRecord {
+            event: event.clone(),
+            kernel_name: kernel_name,
+            enqueued_at: enqueued_at.duration_since(self.start),
+        });
+    }
+
+    /// Calls `f`, recording the time spent as a host span named `name`.
+    pub fn host_span<F, R>(&self, name: &str, f: F) -> R where F: FnOnce() -> R {
+        let start = self.start.elapsed();
+        let result = f();
+        let end = self.start.elapsed();
+
+        self.records.lock().unwrap().host_spans.push(HostSpan {
+            name: name.to_owned(),
+            start: start,
+            end: end,
+        });
+
+        result
+    }
+
+    /// Waits for the events in `events` to complete, recording the time
+    /// spent as a host span named `name`.
+    pub fn wait(&self, name: &str, events: &ClWaitList) -> OclResult<()> {
+        if events.count() == 0 { return Ok(()); }
+        self.host_span(name, || core::wait_for_events(events.count(), events))
+    }
+
+    /// Returns the number of commands recorded.
+    pub fn command_count(&self) -> usize {
+        self.records.lock().unwrap().commands.len()
+    }
+
+    /// Removes all recorded commands and host spans.
+    pub fn clear(&self) {
+        let mut records = self.records.lock().unwrap();
+        records.commands.clear();
+        records.host_spans.clear();
+    }
+
+    /// Waits for each recorded command to complete and returns the timeline
+    /// as Chrome trace event JSON.
+    pub fn to_chrome_trace(&self) -> OclResult<String> {
+        // Wait on a snapshot so that commands can still be recorded (by other
+        // threads or by callbacks) in the meantime:
+        let (commands, host_spans) = {
+            let records = self.records.lock().unwrap();
+            (records.commands.clone(), records.host_spans.clone())
+        };
+
+        let mut lanes: Vec<Lane> = Vec::new();
+        let mut spans: Vec<(usize, CommandSpan)> = Vec::with_capacity(commands.len());
+
+        for cmd in commands.iter() {
+            // Failed commands and commands without timings are omitted:
+            if cmd.event.wait().is_err() { continue; }
+            let timings = match cmd.event.timings() {
+                Ok(t) => t,
+                Err(_) => continue,
+            };
+
+            let queue = match cmd.event.info(EventInfo::CommandQueue) {
+                EventInfoResult::CommandQueue(queue) => queue,
+                EventInfoResult::Error(err) => return Err(*err),
+                _ => return OclError::err("ocl::Timeline::to_chrome_trace(): Unexpected \
+                    'EventInfoResult' variant."),
+            };
+
+            let command_type = match cmd.event.info(EventInfo::CommandType) {
+                EventInfoResult::CommandType(cmd_type) => format!("{:?}", cmd_type),
+                EventInfoResult::Error(err) => return Err(*err),
+                _ => return OclError::err("ocl::Timeline::to_chrome_trace(): Unexpected \
+                    'EventInfoResult' variant."),
+            };
+
+            let queue_ptr = queue.as_ptr() as usize;
+            let lane_idx = match lanes.iter().position(|lane| lane.queue_ptr == queue_ptr) {
+                Some(idx) => idx,
+                None => {
+                    let device_name = match core::get_device_info(&try!(queue_device(&queue)),
+                            DeviceInfo::Name) {
+                        DeviceInfoResult::Name(name) => name,
+                        DeviceInfoResult::Error(err) => return Err(*err),
+                        _ => return OclError::err("ocl::Timeline::to_chrome_trace(): \
+                            Unexpected 'DeviceInfoResult' variant."),
+                    };
+
+                    lanes.push(Lane {
+                        queue_ptr: queue_ptr,
+                        device_name: device_name,
+                        offset_ns: std::i64::MIN,
+                    });
+                    lanes.len() - 1
+                },
+            };
+
+            // The host time at which the command was enqueued precedes the
+            // device time at which it was queued by an unknown amount. Use the
+            // tightest bound:
+            let offset_ns = nanos(cmd.enqueued_at) as i64 - nanos(timings.queued) as i64;
+            if offset_ns > lanes[lane_idx].offset_ns { lanes[lane_idx].offset_ns = offset_ns; }
+
+            spans.push((lane_idx, CommandSpan {
+                name: cmd.kernel_name.clone().unwrap_or(command_type.clone()),
+                command_type: command_type,
+                kernel_name: cmd.kernel_name.clone(),
+                start_ns: nanos(timings.start),
+                end_ns: nanos(timings.end),
+            }));
+        }
+
+        let mut events: Vec<String> = Vec::new();
+        events.push(r#"{"name":"process_name","ph":"M","pid":1,"args":{"name":"ocl"}}"#
+            .to_owned());
+        events.push(r#"{"name":"thread_name","ph":"M","pid":1,"tid":0,"args":{"name":"Host"}}"#
+            .to_owned());
+
+        for (lane_idx, lane) in lanes.iter().enumerate() {
+            events.push(format!("{{\"name\":\"thread_name\",\"ph\":\"M\",\"pid\":1,\
+                \"tid\":{},\"args\":{{\"name\":\"{}\"}}}}", lane_idx + 1,
+                escape(&format!("Queue {} ({})", lane_idx, lane.device_name))));
+        }
+
+        for span in host_spans.iter() {
+            events.push(format!("{{\"name\":\"{}\",\"cat\":\"host\",\"ph\":\"X\",\
+                \"ts\":{:.3},\"dur\":{:.3},\"pid\":1,\"tid\":0}}",
+                escape(&span.name), micros(nanos(span.start) as i64),
+                micros((nanos(span.end) - nanos(span.start)) as i64)));
+        }
+
+        for &(lane_idx, ref span) in spans.iter() {
+            let offset_ns = lanes[lane_idx].offset_ns;
+            let mut args = format!(r#""command_type":"{}""#, escape(&span.command_type));
+            if let Some(ref kernel_name) = span.kernel_name {
+                write!(args, r#","kernel":"{}""#, escape(kernel_name)).unwrap();
+            }
+
+            events.push(format!("{{\"name\":\"{}\",\"cat\":\"command\",\"ph\":\"X\",\
+                \"ts\":{:.3},\"dur\":{:.3},\"pid\":1,\"tid\":{},\"args\":{{{}}}}}",
+                escape(&span.name), micros(span.start_ns as i64 + offset_ns),
+                micros(span.end_ns.saturating_sub(span.start_ns) as i64), lane_idx + 1, args));
+        }
+
+        Ok(format!("{{\"traceEvents\":[\n{}\n],\"displayTimeUnit\":\"ns\"}}\n",
+            events.join(",\n")))
+    }
+
+    /// Waits for each recorded command to complete and writes the timeline
+    /// as Chrome trace event JSON to the file at `path`.
+    pub fn write_chrome_trace<P: AsRef<Path>>(&self, path: P) -> OclResult<()> {
+        let trace = try!(self.to_chrome_trace());
+
+        File::create(path.as_ref()).and_then(|mut file| file.write_all(trace.as_bytes()))
+            .or_else(|err| OclError::err(format!("ocl::Timeline::write_chrome_trace(): Unable to \
+                write '{}': {}", path.as_ref().display(), err)))
+    }
+}
+
+
+/// A queue and the offset from its device's clock to the host clock.
+struct Lane {
+    queue_ptr: usize,
+    device_name: String,
+    offset_ns: i64,
+}
+
+/// A command's span of execution on the device.
+struct CommandSpan {
+    name: String,
+    command_type: String,
+    kernel_name: Option<String>,
+    start_ns: u64,
+    end_ns: u64,
+}
+
+
+fn nanos(duration: Duration) -> u64 {
+    duration.as_secs() * 1_000_000_000 + duration.subsec_nanos() as u64
+}
+
+fn micros(ns: i64) -> f64 {
+    ns as f64 / 1_000.0
+}
+
+/// Escapes `s` for use within a JSON string.
+fn escape(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => write!(escaped, "\\u{:04x}", c as u32).unwrap(),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
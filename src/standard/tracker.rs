@@ -0,0 +1,182 @@
+//! Retention of the objects used by enqueued commands.
+
+use std::any::Any;
+use std::slice;
+use std::sync::Mutex;
+use std::time::Instant;
+use ffi;
+use core::{self, OclPrm, Mem as MemCore, Kernel as KernelCore, Sampler as SamplerCore, ClEventPtrNew,
+    ClWaitList, KernelInfo};
+use core::error::{Error as OclError, Result as OclResult};
+use standard::{Event, Timeline};
+
+
+/// An object retained on behalf of an enqueued command.
+#[derive(Debug)]
+pub enum Resource {
+    Mem(MemCore),
+    Kernel(KernelCore),
+    Sampler(SamplerCore),
+    /// A copy of host memory read by a non-blocking write.
+    HostData(Box<Any + Send>),
+}
+
+
+/// A command which has not yet completed and the objects it uses.
+#[derive(Debug)]
+struct Pending {
+    event: Event,
+    resources: Vec<Resource>,
+}
+
+
+/// Retains the objects (memory objects, kernels, and samplers) used by each
+/// enqueued command until the command completes.
+///
+/// Every `Queue` (and its clones) shares one tracker. Completed commands are
+/// released whenever another command is enqueued using the tracker, or when
+/// `::release_completed` is called (`Queue::finish` does so).
+///
+/// This ensures that dropping a `Buffer`, `Image`, `Kernel`, or `Sampler`
+/// never releases the underlying object while a command is still using it,
+/// whether or not an event was requested for the command. Data passed to
+/// non-blocking writes is copied and the copy retained in the same way
+/// (dropping the tracker blocks until those writes complete). The
+/// destination of a non-blocking read (see `BufferCmd::read_async`) cannot
+/// be retained and must still outlive the command.
+#[derive(Debug)]
+pub struct ResourceTracker {
+    pending: Mutex<Vec<Pending>>,
+    timeline: Mutex<Option<Timeline>>,
+}
+
+impl ResourceTracker {
+    /// Returns a new, empty, tracker.
+    pub fn new() -> ResourceTracker {
+        ResourceTracker { pending: Mutex::new(Vec::new()), timeline: Mutex::new(None) }
+    }
+
+    /// Sets the timeline on which each command enqueued using this tracker
+    /// is recorded (or stops recording if `None`).
+    pub fn set_timeline(&self, timeline: Option<Timeline>) {
+        *self.timeline.lock().unwrap() = timeline;
+    }
+
+    /// Returns the timeline on which commands are being recorded, if any.
+    pub fn timeline(&self) -> Option<Timeline> {
+        self.timeline.lock().unwrap().clone()
+    }
+
+    /// Calls `enqueue` with a new event, retaining `resources` until the
+    /// command it enqueues completes.
+    ///
+    /// The command's event is also stored in `enew`, if specified.
+    pub fn enqueue<F>(&self, resources: Vec<Resource>, enew: Option<&mut ClEventPtrNew>,
+            enqueue: F) -> OclResult<()>
+            where F: FnOnce(Option<&mut ClEventPtrNew>) -> OclResult<()>
+    {
+        self.release_completed();
+
+        let timeline = self.timeline();
+        let enqueued_at = Instant::now();
+
+        let mut event = Event::empty();
+        try!(enqueue(Some(&mut event as &mut ClEventPtrNew)));
+
+        if let Some(timeline) = timeline {
+            let kernel_name = resources.iter().filter_map(|resource| match *resource {
+                Resource::Kernel(ref kernel) => {
+                    let name: String = core::get_kernel_info(kernel, KernelInfo::FunctionName)
+                        .into();
+                    Some(name)
+                },
+                _ => None,
+            }).next();
+
+            timeline.record(&event, kernel_name, enqueued_at);
+        }
+
+        // Blocking commands will already have completed. The command has
+        // been enqueued, so its resources are retained even if copying the
+        // event into `enew` fails:
+        if !resources.is_empty() && !event.is_complete().unwrap_or(true) {
+            self.pending.lock().unwrap().push(Pending { event: event.clone(),
+                resources: resources });
+        }
+
+        if let Some(enew) = enew {
+            try!(copy_event(&event, enew));
+        }
+
+        Ok(())
+    }
+
+    /// Releases the objects used by commands which have completed (or
+    /// failed).
+    pub fn release_completed(&self) {
+        self.pending.lock().unwrap().retain(|pending| {
+            match pending.event.is_complete() {
+                Ok(complete) => !complete,
+                Err(_) => false,
+            }
+        });
+    }
+
+    /// Returns the number of commands which have not yet been found to have
+    /// completed.
+    pub fn pending_count(&self) -> usize {
+        self.pending.lock().unwrap().len()
+    }
+}
+
+
+impl Drop for ResourceTracker {
+    fn drop(&mut self) {
+        // Host data must outlive the writes reading from it:
+        for pending in self.pending.get_mut().unwrap().iter() {
+            let reads_host_data = pending.resources.iter().any(|resource| match *resource {
+                Resource::HostData(_) => true,
+                _ => false,
+            });
+
+            if reads_host_data { let _ = pending.event.wait(); }
+        }
+    }
+}
+
+
+/// Copies `data`, for use by a non-blocking write, into a new resource
+/// added to `resources` and returns the copy.
+///
+/// ## Safety
+///
+/// The returned slice is only valid while the resource remains in (or is
+/// retained by a tracker along with) `resources`.
+pub unsafe fn copy_host_data<'a, T: OclPrm>(data: &[T], resources: &mut Vec<Resource>)
+        -> &'a [T]
+{
+    let copy = data.to_vec();
+    // Moving the vector does not move its contents:
+    let copy_slice = slice::from_raw_parts(copy.as_ptr(), copy.len());
+    resources.push(Resource::HostData(Box::new(copy)));
+    copy_slice
+}
+
+
+/// Stores a new reference to `event` in `enew`.
+fn copy_event(event: &Event, enew: &mut ClEventPtrNew) -> OclResult<()> {
+    unsafe {
+        let event_ptr = *event.as_ptr_ptr();
+        let new_event_ptr = try!(enew.ptr_mut_ptr_new());
+        let errcode = ffi::clRetainEvent(event_ptr);
+
+        if errcode != ffi::CL_SUCCESS {
+            return OclError::err(format!("ocl::ResourceTracker: 'clRetainEvent' failed with \
+                error code: {}.", errcode));
+        }
+
+        *new_event_ptr = event_ptr;
+    }
+
+    Ok(())
+}
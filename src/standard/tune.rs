@@ -0,0 +1,453 @@
+//! Kernel work size and build option auto-tuning.
+
+use std;
+use std::fs::{self, File};
+use std::io::{Read, Write};
+use std::path::PathBuf;
+use core::{self, DeviceInfo, DeviceInfoResult, KernelInfo, KernelInfoResult, ProgramInfo,
+    ProgramInfoResult, ProfilingInfo, ProfilingInfoResult, QUEUE_PROFILING_ENABLE};
+use core::error::{Error as OclError, Result as OclResult};
+use standard::{Kernel, Program, ProgramBuilder, BuildOpt, Queue, SpatialDims, Event};
+
+/// The name of the environment variable which, if set, overrides the default
+/// cache file path.
+pub const CACHE_PATH_VAR: &'static str = "OCL_TUNE_CACHE";
+
+const CACHE_FILE_NAME: &'static str = "ocl_tune_cache.txt";
+
+
+/// A tuned kernel configuration.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TuneConfig {
+    /// The local work size.
+    pub lws: SpatialDims,
+    /// The values of the compiler definitions (`-D`) used to build the
+    /// kernel's program. Empty unless a program template was tuned.
+    pub defines: Vec<(String, String)>,
+}
+
+
+/// The result of tuning a kernel.
+#[derive(Debug)]
+pub struct Tuned {
+    /// The winning configuration.
+    pub config: TuneConfig,
+    /// A kernel with the winning configuration (its default local work
+    /// size set and, if a template was tuned, built with the winning
+    /// definitions) ready to use.
+    pub kernel: Kernel,
+    /// The average run time of the winning configuration in nanoseconds or
+    /// `None` if the configuration was read from the cache.
+    pub time_ns: Option<u64>,
+}
+
+
+/// Times a kernel with each combination of candidate local work sizes and
+/// (optionally) program build definitions and picks the fastest.
+///
+/// The kernel, with its arguments and global work size, is the workload.
+/// Each variant is run once to warm up then timed over `::iters` runs using
+/// profiling events on a profiling-enabled queue created on the same
+/// context and device as the kernel's default queue.
+///
+/// The winner is stored in a cache file keyed by the device name, driver
+/// version, a hash of the program source (and compiler options), the kernel
+/// name, and the global work size. Later runs with the same key return the
+/// cached configuration without timing anything. The cache file is
+/// `ocl_tune_cache.txt` within the system temporary directory unless set
+/// using `::cache_path` or the `OCL_TUNE_CACHE` environment variable.
+///
+/// ## Examples
+///
+/// ```text
+/// let kernel = pro_que.create_kernel("matmul")?
+///     .arg_buf(&a).arg_buf(&b).arg_buf(&c);
+///
+/// let tuned = Tuner::new(&kernel)
+///     .lws([8, 8]).lws([16, 16]).lws([32, 8])
+///     .template(Program::builder().src(MATMUL_SRC))
+///     .define("TILE", &[4, 8, 16])
+///     .run()?;
+///
+/// tuned.kernel.enq()?;
+/// ```
+///
+pub struct Tuner<'k> {
+    kernel: &'k Kernel,
+    lws_candidates: Vec<SpatialDims>,
+    template: Option<ProgramBuilder>,
+    defines: Vec<(String, Vec<String>)>,
+    iters: u32,
+    cache_path: Option<PathBuf>,
+    use_cache: bool,
+}
+
+impl<'k> Tuner<'k> {
+    /// Returns a new tuner for `kernel`.
+    pub fn new(kernel: &'k Kernel) -> Tuner<'k> {
+        Tuner {
+            kernel: kernel,
+            lws_candidates: Vec::new(),
+            template: None,
+            defines: Vec::new(),
+            iters: 10,
+            cache_path: None,
+            use_cache: true,
+        }
+    }
+
+    /// Adds a candidate local work size.
+    pub fn lws<D: Into<SpatialDims>>(mut self, lws: D) -> Tuner<'k> {
+        self.lws_candidates.push(lws.into());
+        self
+    }
+
+    /// Adds several candidate local work sizes.
+    pub fn lws_candidates<D, I>(mut self, candidates: I) -> Tuner<'k>
+            where D: Into<SpatialDims>, I: IntoIterator<Item = D>
+    {
+        self.lws_candidates.extend(candidates.into_iter().map(|lws| lws.into()));
+        self
+    }
+
+    /// Specifies a program template, built once for each combination of the
+    /// values specified using `::define`.
+    ///
+    /// The kernel must take the same arguments in every variant.
+    pub fn template(mut self, template: ProgramBuilder) -> Tuner<'k> {
+        self.template = Some(template);
+        self
+    }
+
+    /// Adds a compiler definition (`-D {name}={val}`) and its candidate
+    /// values, each added to the template as a `BuildOpt::CmplrDefine`.
+    ///
+    /// Values may be anything formattable as a string (integers, floats,
+    /// identifiers, etc.). Values containing a comma, tab, or newline are
+    /// tuned as usual but are not cached.
+    pub fn define<S, V>(mut self, name: S, vals: &[V]) -> Tuner<'k>
+            where S: Into<String>, V: ToString
+    {
+        self.defines.push((name.into(), vals.iter().map(|val| val.to_string()).collect()));
+        self
+    }
+
+    /// Specifies the number of timed runs of each variant (default: 10).
+    pub fn iters(mut self, iters: u32) -> Tuner<'k> {
+        self.iters = std::cmp::max(iters, 1);
+        self
+    }
+
+    /// Specifies the cache file path.
+    pub fn cache_path<P: Into<PathBuf>>(mut self, path: P) -> Tuner<'k> {
+        self.cache_path = Some(path.into());
+        self
+    }
+
+    /// Disables reading from and writing to the cache.
+    pub fn no_cache(mut self) -> Tuner<'k> {
+        self.use_cache = false;
+        self
+    }
+
+    /// Tunes the kernel, or reads the winning configuration from the cache,
+    /// and returns the result.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if no candidates were specified, if definitions
+    /// were specified without a template (or vice versa), if a variant
+    /// fails to build, or if no candidate local work size is valid.
+    pub fn run(&self) -> OclResult<Tuned> {
+        if self.lws_candidates.is_empty() {
+            return OclError::err("ocl::Tuner::run(): No candidate local work sizes specified. \
+                Use '::lws'.");
+        }
+
+        if self.template.is_some() != !self.defines.is_empty() {
+            return OclError::err("ocl::Tuner::run(): A program template ('::template') and \
+                at least one definition ('::define') must be specified together.");
+        }
+
+        let key = try!(self.cache_key());
+        let cache_path = self.cache_path();
+
+        if self.use_cache {
+            if let Some(config) = try!(read_cache(&cache_path)).remove(&key) {
+                let kernel = try!(self.variant(&config.defines)).lws(config.lws);
+                return Ok(Tuned { config: config, kernel: kernel, time_ns: None });
+            }
+        }
+
+        let queue = try!(ProfilingQueue::new(self.kernel));
+        let mut best: Option<(TuneConfig, Kernel, u64)> = None;
+
+        for defines in self.define_combinations() {
+            let kernel = try!(self.variant(&defines));
+
+            for &lws in self.lws_candidates.iter() {
+                // Invalid local work sizes are skipped:
+                let time_ns = match queue.time(&kernel, lws, self.iters) {
+                    Ok(time_ns) => time_ns,
+                    Err(_) => continue,
+                };
+
+                if best.as_ref().map(|&(_, _, best_ns)| time_ns < best_ns).unwrap_or(true) {
+                    let config = TuneConfig { lws: lws, defines: defines.clone() };
+                    best = Some((config, try!(kernel.duplicate()).lws(lws), time_ns));
+                }
+            }
+        }
+
+        let (config, kernel, time_ns) = match best {
+            Some(best) => best,
+            None => return OclError::err(format!("ocl::Tuner::run(): No valid candidate \
+                local work size for kernel '{}' (candidates: {:?}).", self.kernel.name(),
+                self.lws_candidates)),
+        };
+
+        if self.use_cache {
+            let mut cache = try!(read_cache(&cache_path));
+            cache.insert(key, config.clone());
+            try!(write_cache(&cache_path, &cache));
+        }
+
+        Ok(Tuned { config: config, kernel: kernel, time_ns: Some(time_ns) })
+    }
+
+    /// Returns the kernel built with `defines` (or a duplicate of the kernel
+    /// if no template was specified).
+    fn variant(&self, defines: &[(String, String)]) -> OclResult<Kernel> {
+        let template = match self.template {
+            Some(ref template) => defines.iter().fold(template.clone(), |template,
+                    &(ref name, ref val)| {
+                template.bo(BuildOpt::CmplrDefine { ident: name.clone(), val: val.clone() })
+            }),
+            None => return self.kernel.duplicate(),
+        };
+
+        let cmplr_opts = try!(template.get_compiler_options());
+        let queue = self.kernel.default_queue();
+
+        let program = try!(Program::new(try!(template.get_src_strings()), cmplr_opts,
+            queue.context_core_as_ref(), &[queue.device().clone()]));
+
+        self.kernel.rebuild(&program)
+    }
+
+    /// Returns every combination of the candidate definition values.
+    fn define_combinations(&self) -> Vec<Vec<(String, String)>> {
+        self.defines.iter().fold(vec![Vec::new()], |combos, &(ref name, ref vals)| {
+            combos.iter().flat_map(|combo| {
+                vals.iter().map(move |val| {
+                    let mut combo = combo.clone();
+                    combo.push((name.clone(), val.clone()));
+                    combo
+                })
+            }).collect()
+        })
+    }
+
+    /// Returns the cache key for this kernel, program, and device.
+    fn cache_key(&self) -> OclResult<String> {
+        let device = self.kernel.default_queue().device();
+
+        let driver_version = match device.info(DeviceInfo::DriverVersion) {
+            DeviceInfoResult::DriverVersion(v) => v,
+            DeviceInfoResult::Error(err) => return Err(*err),
+            _ => return OclError::err("ocl::Tuner::cache_key(): Unexpected \
+                'DeviceInfoResult' variant."),
+        };
+
+        let source = match self.template {
+            Some(ref template) => {
+                let mut source = Vec::new();
+                for src in try!(template.get_src_strings()) {
+                    source.extend_from_slice(src.as_bytes());
+                }
+                source.extend_from_slice(try!(template.get_compiler_options()).as_bytes());
+                source
+            },
+            None => {
+                let program = match self.kernel.info(KernelInfo::Program) {
+                    KernelInfoResult::Program(program) => program,
+                    KernelInfoResult::Error(err) => return Err(*err),
+                    _ => return OclError::err("ocl::Tuner::cache_key(): Unexpected \
+                        'KernelInfoResult' variant."),
+                };
+
+                match core::get_program_info(&program, ProgramInfo::Source) {
+                    ProgramInfoResult::Source(src) => src.into_bytes(),
+                    ProgramInfoResult::Error(err) => return Err(*err),
+                    _ => return OclError::err("ocl::Tuner::cache_key(): Unexpected \
+                        'ProgramInfoResult' variant."),
+                }
+            },
+        };
+
+        let key = format!("{}|{}|{:016x}|{}|{:?}", device.name().trim(), driver_version.trim(),
+            fnv1a(&source), self.kernel.name(), self.kernel.get_gws());
+
+        // Tabs and newlines delimit the cache file:
+        Ok(key.replace(|c: char| c == '\t' || c == '\n' || c == '\r', " "))
+    }
+
+    fn cache_path(&self) -> PathBuf {
+        match self.cache_path {
+            Some(ref path) => path.clone(),
+            None => match std::env::var_os(CACHE_PATH_VAR) {
+                Some(path) => PathBuf::from(path),
+                None => std::env::temp_dir().join(CACHE_FILE_NAME),
+            },
+        }
+    }
+}
+
+
+/// A profiling-enabled queue on the same context and device as a kernel's
+/// default queue.
+struct ProfilingQueue(Queue);
+
+impl ProfilingQueue {
+    fn new(kernel: &Kernel) -> OclResult<ProfilingQueue> {
+        let queue = try!(kernel.default_queue().with_properties(Some(QUEUE_PROFILING_ENABLE)));
+        Ok(ProfilingQueue(queue))
+    }
+
+    /// Returns the average run time of `kernel` with `lws` in nanoseconds.
+    fn time(&self, kernel: &Kernel, lws: SpatialDims, iters: u32) -> OclResult<u64> {
+        // Warm up:
+        try!(kernel.cmd().queue(&self.0).lws(lws).enq());
+
+        let mut total_ns = 0;
+
+        for _ in 0..iters {
+            let mut event = Event::empty();
+            try!(kernel.cmd().queue(&self.0).lws(lws).enew(&mut event).enq());
+            try!(event.wait());
+            total_ns += try!(profiling_ns(&event, ProfilingInfo::End))
+                .saturating_sub(try!(profiling_ns(&event, ProfilingInfo::Start)));
+        }
+
+        Ok(total_ns / iters as u64)
+    }
+}
+
+
+fn profiling_ns(event: &Event, info_kind: ProfilingInfo) -> OclResult<u64> {
+    match event.profiling_info(info_kind) {
+        ProfilingInfoResult::Start(ns) | ProfilingInfoResult::End(ns) => Ok(ns),
+        ProfilingInfoResult::Error(err) => Err(*err),
+        _ => OclError::err("ocl::Tuner: Unexpected 'ProfilingInfoResult' variant."),
+    }
+}
+
+/// A 64-bit FNV-1a hash, which (unlike `DefaultHasher`) is stable between
+/// Rust versions.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, &byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// Reads the cache file, ignoring malformed lines. A missing file is empty.
+fn read_cache(path: &PathBuf) -> OclResult<std::collections::BTreeMap<String, TuneConfig>> {
+    let mut cache = std::collections::BTreeMap::new();
+
+    let mut contents = String::new();
+    match File::open(path) {
+        Ok(mut file) => {
+            try!(file.read_to_string(&mut contents).map_err(|err| format!("ocl::Tuner: \
+                Unable to read cache file '{}': {}", path.display(), err)));
+        },
+        Err(_) => return Ok(cache),
+    }
+
+    for line in contents.lines() {
+        let mut fields = line.split('\t');
+
+        if let (Some(key), Some(lws), Some(defines)) = (fields.next(), fields.next(),
+                fields.next()) {
+            if let (Some(lws), Some(defines)) = (parse_lws(lws), parse_defines(defines)) {
+                cache.insert(key.to_owned(), TuneConfig { lws: lws, defines: defines });
+            }
+        }
+    }
+
+    Ok(cache)
+}
+
+/// Writes the cache file, replacing any existing file.
+///
+/// The contents are written to a temporary file alongside it which is then
+/// renamed into place so that concurrent tuners never see a partially
+/// written cache.
+fn write_cache(path: &PathBuf, cache: &std::collections::BTreeMap<String, TuneConfig>)
+        -> OclResult<()>
+{
+    let mut contents = String::new();
+
+    for (key, config) in cache.iter() {
+        let lws = match config.lws.to_lens() {
+            Ok(lens) => lens[..config.lws.dim_count() as usize].iter()
+                .map(|len| len.to_string()).collect::<Vec<_>>().join("x"),
+            Err(_) => continue,
+        };
+
+        // Tabs and newlines delimit entries and commas delimit definitions:
+        if config.defines.iter().any(|&(_, ref val)| val.contains(|c: char| c == ',' ||
+                c == '\t' || c == '\n' || c == '\r')) {
+            continue;
+        }
+
+        let defines = if config.defines.is_empty() {
+            "-".to_owned()
+        } else {
+            config.defines.iter().map(|&(ref name, ref val)| format!("{}={}", name, val))
+                .collect::<Vec<_>>().join(",")
+        };
+
+        contents.push_str(&format!("{}\t{}\t{}\n", key, lws, defines));
+    }
+
+    let mut tmp_path = path.clone().into_os_string();
+    tmp_path.push(format!(".{}.tmp", std::process::id()));
+    let tmp_path = PathBuf::from(tmp_path);
+
+    File::create(&tmp_path)
+        .and_then(|mut file| file.write_all(contents.as_bytes()))
+        .and_then(|_| fs::rename(&tmp_path, path))
+        .map_err(|err| {
+            let _ = fs::remove_file(&tmp_path);
+            OclError::from(format!("ocl::Tuner: Unable to write cache file '{}': {}",
+                path.display(), err))
+        })
+}
+
+fn parse_lws(lws: &str) -> Option<SpatialDims> {
+    let lens: Vec<usize> = match lws.split('x').map(|len| len.parse()).collect() {
+        Ok(lens) => lens,
+        Err(_) => return None,
+    };
+
+    match lens.len() {
+        1 => Some(SpatialDims::One(lens[0])),
+        2 => Some(SpatialDims::Two(lens[0], lens[1])),
+        3 => Some(SpatialDims::Three(lens[0], lens[1], lens[2])),
+        _ => None,
+    }
+}
+
+fn parse_defines(defines: &str) -> Option<Vec<(String, String)>> {
+    if defines == "-" {
+        return Some(Vec::new());
+    }
+
+    defines.split(',').map(|define| {
+        let mut parts = define.splitn(2, '=');
+        match (parts.next(), parts.next()) {
+            (Some(name), Some(val)) => Some((name.to_owned(), val.to_owned())),
+            _ => None,
+        }
+    }).collect()
+}
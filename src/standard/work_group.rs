@@ -0,0 +1,305 @@
+//! Work group size selection.
+
+use std::cmp;
+use core::{self, Kernel as KernelCore, CommandQueue as CommandQueueCore,
+    DeviceId as DeviceIdCore, KernelArg, CommandQueueInfo, CommandQueueInfoResult, DeviceInfo,
+    DeviceInfoResult, KernelWorkGroupInfo, KernelWorkGroupInfoResult, ClUint2, ClUint4};
+use core::error::{Result as OclResult, Error as OclError};
+
+
+/// The work group size limits of a kernel on a particular device.
+#[derive(Clone, Debug)]
+pub struct WorkGroupLimits {
+    /// The maximum number of work items in a work group for the kernel.
+    pub max_wg_size: usize,
+    /// The maximum number of work items in a work group for the device.
+    pub device_max_wg_size: usize,
+    /// The preferred multiple of the work group size for the kernel.
+    pub preferred_multiple: usize,
+    /// The maximum number of work items in each dimension of a work group.
+    pub max_item_sizes: Vec<usize>,
+    /// The maximum number of work item dimensions.
+    pub max_item_dims: u32,
+    /// The work group size required by the kernel (`reqd_work_group_size`)
+    /// or all zeros if unspecified.
+    pub compile_wg_size: [usize; 3],
+}
+
+impl WorkGroupLimits {
+    /// Queries the limits of `kernel` on the device associated with `queue`.
+    pub fn query(kernel: &KernelCore, queue: &CommandQueueCore) -> OclResult<WorkGroupLimits> {
+        let device = try!(queue_device(queue));
+
+        let max_wg_size = match core::get_kernel_work_group_info(kernel, &device,
+                KernelWorkGroupInfo::WorkGroupSize) {
+            KernelWorkGroupInfoResult::WorkGroupSize(s) => s,
+            KernelWorkGroupInfoResult::Error(err) => return Err(*err),
+            _ => return OclError::err("ocl::WorkGroupLimits::query(): Unexpected \
+                'KernelWorkGroupInfoResult' variant."),
+        };
+
+        let compile_wg_size = match core::get_kernel_work_group_info(kernel, &device,
+                KernelWorkGroupInfo::CompileWorkGroupSize) {
+            KernelWorkGroupInfoResult::CompileWorkGroupSize(s) => s,
+            KernelWorkGroupInfoResult::Error(err) => return Err(*err),
+            _ => return OclError::err("ocl::WorkGroupLimits::query(): Unexpected \
+                'KernelWorkGroupInfoResult' variant."),
+        };
+
+        let preferred_multiple = match core::get_kernel_work_group_info(kernel, &device,
+                KernelWorkGroupInfo::PreferredWorkGroupSizeMultiple) {
+            KernelWorkGroupInfoResult::PreferredWorkGroupSizeMultiple(s) => s,
+            KernelWorkGroupInfoResult::Error(err) => return Err(*err),
+            _ => return OclError::err("ocl::WorkGroupLimits::query(): Unexpected \
+                'KernelWorkGroupInfoResult' variant."),
+        };
+
+        let max_item_sizes = match core::get_device_info(&device, DeviceInfo::MaxWorkItemSizes) {
+            DeviceInfoResult::MaxWorkItemSizes(s) => s,
+            DeviceInfoResult::Error(err) => return Err(*err),
+            _ => return OclError::err("ocl::WorkGroupLimits::query(): Unexpected \
+                'DeviceInfoResult' variant."),
+        };
+
+        let device_max_wg_size = match core::get_device_info(&device,
+                DeviceInfo::MaxWorkGroupSize) {
+            DeviceInfoResult::MaxWorkGroupSize(s) => s,
+            DeviceInfoResult::Error(err) => return Err(*err),
+            _ => return OclError::err("ocl::WorkGroupLimits::query(): Unexpected \
+                'DeviceInfoResult' variant."),
+        };
+
+        let max_item_dims = match core::get_device_info(&device,
+                DeviceInfo::MaxWorkItemDimensions) {
+            DeviceInfoResult::MaxWorkItemDimensions(d) => d,
+            DeviceInfoResult::Error(err) => return Err(*err),
+            _ => return OclError::err("ocl::WorkGroupLimits::query(): Unexpected \
+                'DeviceInfoResult' variant."),
+        };
+
+        Ok(WorkGroupLimits {
+            max_wg_size: max_wg_size,
+            device_max_wg_size: device_max_wg_size,
+            preferred_multiple: preferred_multiple,
+            max_item_sizes: max_item_sizes,
+            max_item_dims: max_item_dims,
+            compile_wg_size: compile_wg_size,
+        })
+    }
+
+    /// Returns true if the kernel requires a particular work group size.
+    pub fn has_compile_wg_size(&self) -> bool {
+        self.compile_wg_size != [0, 0, 0]
+    }
+
+    /// Returns a local work size suitable for `gws`.
+    ///
+    /// The required work group size is returned if the kernel specifies one.
+    /// Unless `padded`, every dimension of the returned size evenly divides
+    /// the corresponding dimension of `gws`. When `padded`, `gws` is
+    /// expected to be padded up to a multiple of the returned size and the
+    /// first dimension is a multiple of the preferred work group size
+    /// multiple where possible.
+    pub fn auto_lws(&self, gws: &[usize; 3], dim_count: u32, padded: bool) -> [usize; 3] {
+        if self.has_compile_wg_size() {
+            return self.compile_wg_size;
+        }
+
+        let preferred_multiple = cmp::max(self.preferred_multiple, 1);
+        let mut lws = [1; 3];
+        let mut budget = cmp::max(self.max_wg_size, 1);
+
+        for dim in 0..(dim_count as usize) {
+            let max_items = self.max_item_sizes.get(dim).cloned().unwrap_or(1);
+            let limit = cmp::max(cmp::min(budget, max_items), 1);
+            let len = cmp::max(gws[dim], 1);
+
+            lws[dim] = if padded {
+                if dim == 0 {
+                    let cap = cmp::min(limit, round_up(len, preferred_multiple));
+                    match (cap / preferred_multiple) * preferred_multiple {
+                        0 => prev_power_of_two(cap),
+                        size => size,
+                    }
+                } else {
+                    prev_power_of_two(cmp::min(limit, len.next_power_of_two()))
+                }
+            } else {
+                let limit = cmp::min(limit, len);
+                let preferred = if dim == 0 {
+                    (1..(limit / preferred_multiple) + 1).rev()
+                        .map(|m| m * preferred_multiple)
+                        .find(|&size| len % size == 0)
+                } else {
+                    None
+                };
+
+                preferred.unwrap_or_else(|| {
+                    (1..limit + 1).rev().find(|&size| len % size == 0).unwrap_or(1)
+                })
+            };
+
+            budget /= lws[dim];
+        }
+
+        lws
+    }
+
+    /// Checks that `gws` and `lws` are valid for the kernel and device.
+    pub fn validate(&self, gws: &[usize; 3], lws: Option<&[usize; 3]>, dim_count: u32)
+            -> OclResult<()>
+    {
+        if dim_count > self.max_item_dims {
+            return OclError::err(format!("The work size dimension count ({}) exceeds the \
+                device maximum ('MaxWorkItemDimensions': {}).", dim_count, self.max_item_dims));
+        }
+
+        let lws = match lws {
+            Some(lws) => lws,
+            None => {
+                if self.has_compile_wg_size() {
+                    return OclError::err(format!("The kernel requires a work group size of \
+                        {:?} ('CompileWorkGroupSize') but no local work size ('lws') was \
+                        specified.", self.compile_wg_size));
+                }
+                return Ok(());
+            },
+        };
+
+        let dims = 0..(dim_count as usize);
+
+        if self.has_compile_wg_size() && dims.clone().any(|d| lws[d] != self.compile_wg_size[d]) {
+            return OclError::err(format!("The local work size ('lws': {:?}) does not match the \
+                work group size required by the kernel ('CompileWorkGroupSize': {:?}).",
+                &lws[..dim_count as usize], &self.compile_wg_size[..dim_count as usize]));
+        }
+
+        for dim in dims.clone() {
+            if lws[dim] == 0 {
+                return OclError::err(format!("The local work size ('lws': {:?}) is zero in \
+                    dimension {}.", &lws[..dim_count as usize], dim));
+            }
+
+            if let Some(&max_items) = self.max_item_sizes.get(dim) {
+                if lws[dim] > max_items {
+                    return OclError::err(format!("The local work size ('lws': {:?}) exceeds \
+                        the device maximum in dimension {} ('MaxWorkItemSizes': {:?}).",
+                        &lws[..dim_count as usize], dim, self.max_item_sizes));
+                }
+            }
+
+            if gws[dim] % lws[dim] != 0 {
+                return OclError::err(format!("The local work size ('lws': {:?}) does not \
+                    evenly divide the global work size ('gws': {:?}) in dimension {}.",
+                    &lws[..dim_count as usize], &gws[..dim_count as usize], dim));
+            }
+        }
+
+        let wg_size: usize = dims.map(|d| lws[d]).product();
+
+        if wg_size > self.device_max_wg_size {
+            return OclError::err(format!("The work group size ({}, 'lws': {:?}) exceeds the \
+                device maximum ('MaxWorkGroupSize': {}).", wg_size, &lws[..dim_count as usize],
+                self.device_max_wg_size));
+        }
+
+        if wg_size > self.max_wg_size {
+            return OclError::err(format!("The work group size ({}, 'lws': {:?}) exceeds the \
+                maximum for the kernel on this device ('WorkGroupSize': {}).", wg_size,
+                &lws[..dim_count as usize], self.max_wg_size));
+        }
+
+        Ok(())
+    }
+}
+
+
+/// Returns the device associated with `queue`.
+pub fn queue_device(queue: &CommandQueueCore) -> OclResult<DeviceIdCore> {
+    match core::get_command_queue_info(queue, CommandQueueInfo::Device) {
+        CommandQueueInfoResult::Device(device) => Ok(device),
+        CommandQueueInfoResult::Error(err) => Err(*err),
+        _ => OclError::err("ocl::queue_device(): Unexpected 'CommandQueueInfoResult' variant."),
+    }
+}
+
+/// Returns `gws` with each dimension rounded up to a multiple of the
+/// corresponding dimension of `lws`.
+pub fn pad_gws(gws: &[usize; 3], lws: &[usize; 3], dim_count: u32) -> [usize; 3] {
+    let mut padded = *gws;
+
+    for dim in 0..(dim_count as usize) {
+        padded[dim] = round_up(gws[dim], cmp::max(lws[dim], 1));
+    }
+
+    padded
+}
+
+/// Sets the argument at `arg_idx` of `kernel` to the real (unpadded) global
+/// work size, `gws`.
+///
+/// The argument must be declared as a `uint` for one dimension, a `uint2`
+/// for two, or a `uint3` or `uint4` for three. Returns an error if any
+/// dimension does not fit in a `uint`.
+pub fn set_real_gws_arg(kernel: &KernelCore, arg_idx: u32, gws: &[usize; 3], dim_count: u32)
+        -> OclResult<()>
+{
+    let dims = &gws[..cmp::min(dim_count as usize, 3)];
+
+    if let Some(dim) = dims.iter().position(|&size| size as u64 > u32::max_value() as u64) {
+        return OclError::err(format!("ocl::KernelCmd::enq(): The global work size ('gws': \
+            {:?}) is too large to be passed as a 'uint' argument (dimension {} exceeds {}).",
+            dims, dim, u32::max_value()));
+    }
+
+    let (x, y, z) = (gws[0] as u32, gws[1] as u32, gws[2] as u32);
+
+    match dim_count {
+        1 => core::set_kernel_arg::<u32>(kernel, arg_idx, KernelArg::Scalar(x)),
+        2 => core::set_kernel_arg::<ClUint2>(kernel, arg_idx, KernelArg::Vector(ClUint2(x, y))),
+        3 => core::set_kernel_arg::<ClUint4>(kernel, arg_idx,
+            KernelArg::Vector(ClUint4(x, y, z, 1))),
+        _ => OclError::err(format!("ocl::KernelCmd::enq(): Invalid global work size dimension \
+            count ({}).", dim_count)),
+    }
+}
+
+/// Returns the start and length of each chunk, along the last dimension,
+/// of a launch split into chunks of at most `max_work_items` work items.
+///
+/// Chunk lengths are multiples of the local work size in the last dimension
+/// (if specified). Returns a single chunk if no split is necessary.
+pub fn chunk_ranges(gws: &[usize; 3], lws: Option<&[usize; 3]>, dim_count: u32,
+        max_work_items: usize) -> Vec<(usize, usize)>
+{
+    let last_dim = cmp::max(dim_count as usize, 1) - 1;
+    let total_len = gws[last_dim];
+    let row_items: usize = gws[..last_dim].iter().product();
+    let incr = lws.map(|lws| cmp::max(lws[last_dim], 1)).unwrap_or(1);
+
+    // Round down to a multiple of the local work size but never below it:
+    let chunk_len = cmp::max(max_work_items / cmp::max(row_items, 1), 1);
+    let chunk_len = cmp::max((chunk_len / incr) * incr, incr);
+
+    let mut chunks = Vec::with_capacity((total_len + chunk_len - 1) / chunk_len);
+    let mut start = 0;
+
+    while start < total_len {
+        chunks.push((start, cmp::min(chunk_len, total_len - start)));
+        start += chunk_len;
+    }
+
+    chunks
+}
+
+fn round_up(len: usize, incr: usize) -> usize {
+    ((len + incr - 1) / incr) * incr
+}
+
+fn prev_power_of_two(val: usize) -> usize {
+    match val {
+        0 => 1,
+        val if val.is_power_of_two() => val,
+        val => val.next_power_of_two() / 2,
+    }
+}
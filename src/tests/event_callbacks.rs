@@ -0,0 +1,60 @@
+//! Tests closure callbacks on events and event lists.
+
+use std::sync::mpsc;
+use std::time::Duration;
+use core::CommandExecutionStatus;
+use standard::{ProQue, Event, EventList, UserEvent};
+
+static SRC: &'static str = r#"
+    __kernel void add(__global float* buffer, float addend) {
+        buffer[get_global_id(0)] += addend;
+    }
+"#;
+
+#[test]
+fn event_callbacks() {
+    let pro_que = ProQue::builder()
+        .src(SRC)
+        .dims([1024])
+        .build().unwrap();
+
+    let buffer = pro_que.create_buffer::<f32>().unwrap();
+
+    let kernel = pro_que.create_kernel("add").unwrap()
+        .arg_buf(&buffer)
+        .arg_scl(1.0f32);
+
+    let (tx, rx) = mpsc::channel();
+    let timeout = Duration::from_secs(10);
+
+    // Event:
+    let mut event = Event::empty();
+    assert!(event.on_complete(|_| ()).is_err());
+    kernel.cmd().enew(&mut event).enq().unwrap();
+
+    let tx_clone = tx.clone();
+    event.on_complete(move |status| tx_clone.send(("event", status.is_ok())).unwrap()).unwrap();
+    assert!(event.on_status(CommandExecutionStatus::Queued, |_| ()).is_err());
+    assert_eq!(rx.recv_timeout(timeout).unwrap(), ("event", true));
+
+    // The last event of an event list:
+    let mut event_list = EventList::new();
+    assert!(event_list.on_complete(|_| ()).is_err());
+    kernel.cmd().enew(&mut event_list).enq().unwrap();
+    kernel.cmd().enew(&mut event_list).enq().unwrap();
+
+    let tx_clone = tx.clone();
+    event_list.on_complete(move |status| tx_clone.send(("list", status.is_ok())).unwrap())
+        .unwrap();
+    assert_eq!(rx.recv_timeout(timeout).unwrap(), ("list", true));
+
+    // Commands terminated abnormally report an error:
+    let user_event = UserEvent::new(pro_que.context()).unwrap();
+    let tx_clone = tx.clone();
+    user_event.on_complete(move |status| tx_clone.send(("failed", status.is_ok())).unwrap())
+        .unwrap();
+    user_event.set_error(-1).unwrap();
+    assert_eq!(rx.recv_timeout(timeout).unwrap(), ("failed", false));
+
+    pro_que.queue().finish();
+}
@@ -0,0 +1,95 @@
+//! Tests `Future` implementations for events, event lists, and reads.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+use std::thread::{self, Thread};
+use standard::{ProQue, Event, EventList, UserEvent};
+
+static SRC: &'static str = r#"
+    __kernel void add(__global float* buffer, float addend) {
+        buffer[get_global_id(0)] += addend;
+    }
+"#;
+
+static VTABLE: RawWakerVTable = RawWakerVTable::new(clone_waker, wake, wake_by_ref, drop_waker);
+
+unsafe fn clone_waker(ptr: *const ()) -> RawWaker {
+    let thread = Arc::from_raw(ptr as *const Thread);
+    let clone = thread.clone();
+    ::std::mem::forget(thread);
+    RawWaker::new(Arc::into_raw(clone) as *const (), &VTABLE)
+}
+
+unsafe fn wake(ptr: *const ()) {
+    Arc::from_raw(ptr as *const Thread).unpark();
+}
+
+unsafe fn wake_by_ref(ptr: *const ()) {
+    (*(ptr as *const Thread)).unpark();
+}
+
+unsafe fn drop_waker(ptr: *const ()) {
+    drop(Arc::from_raw(ptr as *const Thread));
+}
+
+/// Runs `future` to completion on the current thread, parking until woken.
+fn block_on<F: Future>(mut future: F) -> F::Output {
+    let raw = RawWaker::new(Arc::into_raw(Arc::new(thread::current())) as *const (), &VTABLE);
+    let waker = unsafe { Waker::from_raw(raw) };
+    let mut cx = Context::from_waker(&waker);
+    let mut future = unsafe { Pin::new_unchecked(&mut future) };
+
+    loop {
+        match future.as_mut().poll(&mut cx) {
+            Poll::Ready(output) => return output,
+            Poll::Pending => thread::park(),
+        }
+    }
+}
+
+#[test]
+fn event_future() {
+    let pro_que = ProQue::builder()
+        .src(SRC)
+        .dims([1024])
+        .build().unwrap();
+
+    let buffer = pro_que.create_buffer::<f32>().unwrap();
+
+    let kernel = pro_que.create_kernel("add").unwrap()
+        .arg_buf(&buffer)
+        .arg_scl(1.0f32);
+
+    // Event (held back until a user event is completed from another
+    // thread):
+    let user_event = UserEvent::new(pro_que.context()).unwrap();
+    let mut event = Event::empty();
+    kernel.cmd().ewait(&user_event).enew(&mut event).enq().unwrap();
+
+    let setter = thread::spawn(move || user_event.set_complete().unwrap());
+    block_on(event.future()).unwrap();
+    setter.join().unwrap();
+
+    // Empty events resolve to an error:
+    assert!(block_on(Event::empty().future()).is_err());
+
+    // Event list:
+    let mut event_list = EventList::new();
+    kernel.cmd().enew(&mut event_list).enq().unwrap();
+    kernel.cmd().enew(&mut event_list).enq().unwrap();
+    block_on(event_list.future()).unwrap();
+
+    // Failed commands resolve to an error:
+    let user_event = UserEvent::new(pro_que.context()).unwrap();
+    let mut event_list = EventList::new();
+    event_list.push(user_event.as_event().clone());
+    user_event.set_error(-1).unwrap();
+    assert!(block_on(event_list.future()).is_err());
+
+    // Reads:
+    let vec = block_on(buffer.read_async()).unwrap();
+    assert_eq!(vec.len(), buffer.len());
+    assert!(vec.iter().all(|&val| val == 3.0));
+}
@@ -0,0 +1,94 @@
+//! Tests indexing, iterating, and waiting on event lists.
+
+use std::thread;
+use std::time::Duration;
+use core::CommandExecutionStatus;
+use standard::{ProQue, Event, EventList, UserEvent};
+
+static SRC: &'static str = r#"
+    __kernel void add(__global float* buffer, float addend) {
+        buffer[get_global_id(0)] += addend;
+    }
+"#;
+
+#[test]
+fn event_list() {
+    let pro_que = ProQue::builder()
+        .src(SRC)
+        .dims([1024])
+        .build().unwrap();
+
+    let buffer = pro_que.create_buffer::<f32>().unwrap();
+
+    let kernel = pro_que.create_kernel("add").unwrap()
+        .arg_buf(&buffer)
+        .arg_scl(1.0f32);
+
+    // Indexing and iteration:
+    let mut events = EventList::new();
+    for _ in 0..3 {
+        kernel.cmd().enew(&mut events).enq().unwrap();
+    }
+    events.wait().unwrap();
+
+    let first: &Event = &events[0];
+    assert!(first.is_complete().unwrap());
+    assert!(events[2].is_complete().unwrap());
+    assert_eq!(events.iter().len(), 3);
+    assert!(events.iter().all(|event| event.is_complete().unwrap()));
+    assert_eq!((&events).into_iter().count(), 3);
+
+    let last = events.pop().unwrap();
+    assert_eq!(events.iter().count(), 2);
+    events.push(last);
+    assert!(events[2].is_complete().unwrap());
+
+    let statuses = events.statuses();
+    assert_eq!(statuses.len(), 3);
+    for status in statuses {
+        match status {
+            Ok(CommandExecutionStatus::Complete) => (),
+            other => panic!("Unexpected status: {:?}", other),
+        }
+    }
+
+    // Collecting, extending, and consuming:
+    let mut collected: EventList = events.clone().into_iter().collect();
+    assert_eq!(collected.len(), 3);
+    collected.extend(events.clone().into_iter().take(2));
+    assert_eq!(collected.len(), 5);
+
+    let owned: Vec<Event> = collected.into_iter().collect();
+    assert_eq!(owned.len(), 5);
+    assert!(owned.iter().all(|event| event.is_complete().unwrap()));
+
+    // Waiting for any one event (a user event completed from another
+    // thread while a second is never completed until the end):
+    let never = UserEvent::new(pro_que.context()).unwrap();
+    let soon = UserEvent::new(pro_que.context()).unwrap();
+    let list: EventList = vec![never.as_event().clone(), soon.as_event().clone()]
+        .into_iter().collect();
+
+    assert!(!list.wait_timeout(Duration::from_millis(20)).unwrap());
+
+    let setter = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(20));
+        soon.set_complete().unwrap();
+    });
+    assert_eq!(list.wait_any().unwrap(), 1);
+    setter.join().unwrap();
+
+    assert!(!list.wait_timeout(Duration::from_millis(20)).unwrap());
+    never.set_complete().unwrap();
+    assert!(list.wait_timeout(Duration::from_secs(10)).unwrap());
+
+    // Failed events are reported by `statuses`:
+    let failed = UserEvent::new(pro_que.context()).unwrap();
+    failed.set_error(-5).unwrap();
+    let list: EventList = vec![failed.as_event().clone()].into_iter().collect();
+    assert_eq!(list.wait_any().unwrap(), 0);
+    assert!(list.statuses()[0].is_err());
+
+    assert!(EventList::new().wait_any().is_err());
+    assert!(EventList::new().wait_timeout(Duration::from_millis(1)).unwrap());
+}
@@ -0,0 +1,78 @@
+//! Tests event timings and the profiler.
+
+use core::QUEUE_PROFILING_ENABLE;
+use standard::{ProQue, Event, Profiler};
+
+static SRC: &'static str = r#"
+    __kernel void add(__global float* buffer, float addend) {
+        buffer[get_global_id(0)] += addend;
+    }
+"#;
+
+#[test]
+fn event_profiling() {
+    let pro_que = ProQue::builder()
+        .src(SRC)
+        .dims([4096])
+        .queue_properties(QUEUE_PROFILING_ENABLE)
+        .build().unwrap();
+
+    let buffer = pro_que.create_buffer::<f32>().unwrap();
+
+    let kernel = pro_que.create_kernel("add").unwrap()
+        .arg_buf(&buffer)
+        .arg_scl(1.0f32);
+
+    // Timings:
+    let mut event = Event::empty();
+    kernel.cmd().enew(&mut event).enq().unwrap();
+    event.wait().unwrap();
+
+    let timings = event.timings().unwrap();
+    assert!(timings.queued <= timings.submit);
+    assert!(timings.submit <= timings.start);
+    assert!(timings.start <= timings.end);
+    assert_eq!(timings.total(), timings.latency() + timings.duration());
+
+    assert!(Event::empty().timings().is_err());
+
+    // Profiler:
+    let mut profiler = Profiler::new();
+    let mut vec = vec![0.0f32; buffer.len()];
+
+    for _ in 0..10 {
+        let mut event = Event::empty();
+        kernel.cmd().enew(&mut event).enq().unwrap();
+        profiler.add_kernel(&kernel, event);
+
+        let mut event = Event::empty();
+        buffer.read(&mut vec).enew(&mut event).enq().unwrap();
+        profiler.add_command(event).unwrap();
+    }
+
+    assert_eq!(profiler.len(), 20);
+    let stats = profiler.stats().unwrap();
+    assert_eq!(stats.len(), 2);
+
+    for s in stats.iter() {
+        assert_eq!(s.count, 10);
+        assert!(s.min <= s.p50 && s.p50 <= s.p90 && s.p90 <= s.p99 && s.p99 <= s.max);
+        assert!(s.min <= s.mean && s.mean <= s.max);
+    }
+
+    assert!(stats.iter().any(|s| s.tag == "add"));
+    let table = profiler.table().unwrap();
+    assert_eq!(table.lines().count(), 3);
+    assert!(table.contains("add"));
+
+    // Queues without profiling enabled:
+    let pro_que = ProQue::builder()
+        .src(SRC)
+        .dims([4096])
+        .build().unwrap();
+
+    let buffer = pro_que.create_buffer::<f32>().unwrap();
+    let mut event = Event::empty();
+    buffer.read(&mut vec).enew(&mut event).enq().unwrap();
+    assert!(event.timings().is_err());
+}
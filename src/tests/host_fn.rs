@@ -0,0 +1,85 @@
+//! Tests host functions (native kernels) enqueued on a queue.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use standard::{ProQue, Event, UserEvent};
+
+static SRC: &'static str = r#"
+    __kernel void add(__global float* buffer, float addend) {
+        buffer[get_global_id(0)] += addend;
+    }
+"#;
+
+#[test]
+fn host_fn() {
+    let pro_que = ProQue::builder()
+        .src(SRC)
+        .dims([1024])
+        .build().unwrap();
+
+    let buffer = pro_que.create_buffer::<f32>().unwrap();
+
+    let kernel = pro_que.create_kernel("add").unwrap()
+        .arg_buf(&buffer)
+        .arg_scl(1.0f32);
+
+    // A plain closure runs after the commands enqueued before it:
+    let calls = Arc::new(AtomicUsize::new(0));
+    let calls_clone = calls.clone();
+    let mut event = Event::empty();
+
+    kernel.enq().unwrap();
+    pro_que.queue().enqueue_host_fn(move || { calls_clone.fetch_add(1, Ordering::SeqCst); },
+        None, Some(&mut event)).unwrap();
+    event.wait().unwrap();
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+    // A closure receiving the buffer contents sees the results of the
+    // preceding kernel and the following kernel sees its changes:
+    kernel.enq().unwrap();
+    pro_que.queue().enqueue_host_fn_mem(&[&buffer], |bufs| {
+        assert!(bufs[0].iter().all(|&val| val == 2.0));
+        for val in bufs[0].iter_mut() { *val *= 10.0; }
+    }, None, None).unwrap();
+    kernel.enq().unwrap();
+
+    let mut vec = vec![0.0f32; buffer.len()];
+    buffer.read(&mut vec).enq().unwrap();
+    assert!(vec.iter().all(|&val| val == 21.0));
+}
+
+#[test]
+fn host_fn_failed_wait_event() {
+    let pro_que = ProQue::builder()
+        .src(SRC)
+        .dims([1024])
+        .build().unwrap();
+
+    let buffer = pro_que.create_buffer::<f32>().unwrap();
+
+    let kernel = pro_que.create_kernel("add").unwrap()
+        .arg_buf(&buffer)
+        .arg_scl(1.0f32);
+
+    // The closure is skipped and the failure is reported through the new
+    // event:
+    let calls = Arc::new(AtomicUsize::new(0));
+    let calls_clone = calls.clone();
+    let user_event = UserEvent::new(pro_que.context()).unwrap();
+    let mut event = Event::empty();
+
+    pro_que.queue().enqueue_host_fn_mem(&[&buffer], move |_| {
+        calls_clone.fetch_add(1, Ordering::SeqCst);
+    }, Some(&user_event), Some(&mut event)).unwrap();
+
+    user_event.set_error(-1).unwrap();
+    assert!(event.wait().is_err());
+    assert_eq!(calls.load(Ordering::SeqCst), 0);
+
+    // The buffer has been unmapped and remains usable:
+    kernel.cmd().enq().unwrap();
+
+    let mut vec = vec![0.0f32; buffer.len()];
+    buffer.read(&mut vec).enq().unwrap();
+    assert!(vec.iter().all(|&val| val == 1.0));
+}
@@ -0,0 +1,159 @@
+//! Tests per-layer access to 1d and 2d image arrays.
+
+use standard::{ProQue, Image};
+use enums::{ImageChannelOrder, ImageChannelDataType, MemObjectType};
+
+const WIDTH: usize = 32;
+const HEIGHT: usize = 16;
+const ARRAY_SIZE: usize = 5;
+
+fn layer_val(layer_idx: usize) -> i32 {
+    (layer_idx as i32 + 1) * 10
+}
+
+#[test]
+fn image_2d_array_layers() {
+    let proque = ProQue::builder()
+        .src("__kernel void nop() {}")
+        .dims([WIDTH, HEIGHT])
+        .build().unwrap();
+
+    let img = Image::<i32>::builder()
+        .channel_order(ImageChannelOrder::Rgba)
+        .channel_data_type(ImageChannelDataType::SignedInt32)
+        .image_type(MemObjectType::Image2dArray)
+        .dims([WIDTH, HEIGHT])
+        .array_size(ARRAY_SIZE)
+        .build(proque.queue().clone()).unwrap();
+
+    assert_eq!(img.array_size(), ARRAY_SIZE);
+    assert!(img.layer(ARRAY_SIZE).is_err());
+
+    let props = img.properties();
+    assert_eq!(props.descriptor.image_array_size, ARRAY_SIZE);
+    assert_eq!(props.format.channel_order, ImageChannelOrder::Rgba);
+    assert_eq!(props.element_size, 16);
+    assert!(props.row_pitch >= WIDTH * props.element_size);
+    assert!(props.slice_pitch >= props.row_pitch * HEIGHT);
+
+    let layers = img.layers().unwrap();
+    assert_eq!(layers.len(), ARRAY_SIZE);
+
+    // Write a distinct value to each layer:
+    for layer in layers.iter() {
+        assert_eq!(layer.origin(), [0, 0, layer.idx()]);
+        assert_eq!(layer.region(), [WIDTH, HEIGHT, 1]);
+        let vec = vec![layer_val(layer.idx()); layer.element_count()];
+        layer.write(&vec).enq().unwrap();
+    }
+
+    // Verify that writes did not spill over into neighboring layers:
+    let mut vec = vec![0i32; WIDTH * HEIGHT * img.pixel_element_len()];
+    for layer in layers.iter() {
+        layer.read(&mut vec).enq().unwrap();
+        for &ele in vec.iter() {
+            assert_eq!(ele, layer_val(layer.idx()));
+        }
+    }
+
+    // Fill the last layer then copy it to the first:
+    let fill_val = -7i32;
+    layers[ARRAY_SIZE - 1].fill(fill_val).enq().unwrap();
+    layers[ARRAY_SIZE - 1].copy(&layers[0]).enq().unwrap();
+
+    for &layer_idx in &[0, ARRAY_SIZE - 1] {
+        img.layer(layer_idx).unwrap().read(&mut vec).enq().unwrap();
+        for &ele in vec.iter() {
+            assert_eq!(ele, fill_val);
+        }
+    }
+
+    // Untouched layers should be unchanged:
+    img.layer(1).unwrap().read(&mut vec).enq().unwrap();
+    for &ele in vec.iter() {
+        assert_eq!(ele, layer_val(1));
+    }
+}
+
+#[test]
+fn image_2d_array_layer_fill_unorm() {
+    let proque = ProQue::builder()
+        .src("__kernel void nop() {}")
+        .dims([WIDTH, HEIGHT])
+        .build().unwrap();
+
+    let img = Image::<u8>::builder()
+        .channel_order(ImageChannelOrder::Rgba)
+        .channel_data_type(ImageChannelDataType::UnormInt8)
+        .image_type(MemObjectType::Image2dArray)
+        .dims([WIDTH, HEIGHT])
+        .array_size(ARRAY_SIZE)
+        .build(proque.queue().clone()).unwrap();
+
+    let layers = img.layers().unwrap();
+    let mut vec = vec![0u8; WIDTH * HEIGHT * img.pixel_element_len()];
+    layers[0].write(&vec).enq().unwrap();
+
+    // Normalized formats are filled with a 'float4' converted from the
+    // stored value (200 / 255):
+    layers[1].fill(200u8).enq().unwrap();
+    layers[1].read(&mut vec).enq().unwrap();
+    for &ele in vec.iter() {
+        assert_eq!(ele, 200);
+    }
+
+    layers[0].read(&mut vec).enq().unwrap();
+    for &ele in vec.iter() {
+        assert_eq!(ele, 0);
+    }
+}
+
+#[test]
+fn image_1d_array_layers() {
+    let proque = ProQue::builder()
+        .src("__kernel void nop() {}")
+        .dims([WIDTH])
+        .build().unwrap();
+
+    let img = Image::<i32>::builder()
+        .channel_order(ImageChannelOrder::Rgba)
+        .channel_data_type(ImageChannelDataType::SignedInt32)
+        .image_type(MemObjectType::Image1dArray)
+        .dims([WIDTH])
+        .array_size(ARRAY_SIZE)
+        .build(proque.queue().clone()).unwrap();
+
+    for layer in img.layers().unwrap().iter() {
+        assert_eq!(layer.origin(), [0, layer.idx(), 0]);
+        assert_eq!(layer.region(), [WIDTH, 1, 1]);
+        let vec = vec![layer_val(layer.idx()); layer.element_count()];
+        layer.write(&vec).enq().unwrap();
+    }
+
+    let mut vec = vec![0i32; WIDTH * img.pixel_element_len()];
+    for layer in img.layers().unwrap().iter() {
+        layer.read(&mut vec).enq().unwrap();
+        for &ele in vec.iter() {
+            assert_eq!(ele, layer_val(layer.idx()));
+        }
+    }
+}
+
+#[test]
+fn image_non_array_has_no_layers() {
+    let proque = ProQue::builder()
+        .src("__kernel void nop() {}")
+        .dims([WIDTH, HEIGHT])
+        .build().unwrap();
+
+    let img = Image::<i32>::builder()
+        .channel_order(ImageChannelOrder::Rgba)
+        .channel_data_type(ImageChannelDataType::SignedInt32)
+        .image_type(MemObjectType::Image2d)
+        .dims([WIDTH, HEIGHT])
+        .build(proque.queue().clone()).unwrap();
+
+    assert_eq!(img.array_size(), 0);
+    assert!(img.layer(0).is_err());
+    assert!(img.layers().is_err());
+}
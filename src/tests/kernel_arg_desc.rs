@@ -0,0 +1,55 @@
+//! Tests kernel argument descriptions and `Display` output.
+
+use core::ClFloat4;
+use standard::{ProQue, Program, KernelArgKind};
+
+static SRC: &'static str = r#"
+    __kernel void describe(__global float* buffer, float scalar, float4 vector,
+            __local uint* scratch, int unset)
+    {
+        buffer[get_global_id(0)] = scalar + vector.x;
+    }
+"#;
+
+#[test]
+fn kernel_arg_desc() {
+    let pro_que = ProQue::builder()
+        .prog_bldr(Program::builder().src(SRC).cmplr_opt("-cl-kernel-arg-info"))
+        .dims([256])
+        .build().unwrap();
+
+    let buffer = pro_que.create_buffer::<f32>().unwrap();
+
+    let kernel = pro_que.create_kernel("describe").unwrap()
+        .arg_buf(&buffer)
+        .arg_scl(2.5f32)
+        .arg_vec(ClFloat4(1.0, 2.0, 3.0, 4.0))
+        .arg_loc::<u32>(64);
+
+    let args = kernel.args();
+    assert_eq!(args.len(), 5);
+
+    assert_eq!(args[0].kind, KernelArgKind::Buffer);
+    assert_eq!(args[0].type_name, Some("f32"));
+    assert_eq!(args[0].mem_len, Some(256));
+    assert_eq!(args[0].value, None);
+
+    assert_eq!(args[1].kind, KernelArgKind::Scalar);
+    assert_eq!(args[1].type_name, Some("f32"));
+    assert_eq!(args[1].value, Some(format!("{:?}", 2.5f32)));
+
+    assert_eq!(args[2].kind, KernelArgKind::Vector);
+    assert_eq!(args[2].type_name, Some("ClFloat4"));
+    assert_eq!(args[2].value, Some(format!("{:?}", ClFloat4(1.0, 2.0, 3.0, 4.0))));
+
+    assert_eq!(args[3].kind, KernelArgKind::Local);
+    assert_eq!(args[3].type_name, Some("u32"));
+    assert_eq!(args[3].mem_len, Some(64));
+
+    assert_eq!(args[4].kind, KernelArgKind::Unset);
+
+    let display = kernel.to_string();
+    assert!(display.contains("scratch"), "{}", display);
+    assert!(display.contains("float4"), "{}", display);
+    assert!(display.contains("Unset"), "{}", display);
+}
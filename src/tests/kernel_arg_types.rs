@@ -0,0 +1,95 @@
+//! Tests validation of kernel arguments against `KernelArgInfo`.
+
+use standard::{ProQue, Program, Kernel};
+use core::KernelArg;
+
+static SRC: &'static str = r#"
+    __kernel void add(__global float* buffer, float addend, __local float* scratch) {
+        scratch[get_local_id(0)] = buffer[get_global_id(0)];
+        buffer[get_global_id(0)] = scratch[get_local_id(0)] + addend;
+    }
+
+    __kernel void add_ulong(__global ulong* buffer, ulong addend) {
+        buffer[get_global_id(0)] += addend;
+    }
+"#;
+
+fn pro_que() -> ProQue {
+    ProQue::builder()
+        .prog_bldr(Program::builder().src(SRC).cmplr_opt("-cl-kernel-arg-info"))
+        .dims([1024])
+        .build().unwrap()
+}
+
+fn kernel_add(pro_que: &ProQue) -> Kernel {
+    let buffer = pro_que.create_buffer::<f32>().unwrap();
+    pro_que.create_kernel("add").unwrap()
+        .arg_buf(&buffer)
+        .arg_scl(10.0f32)
+        .arg_loc::<f32>(64)
+}
+
+#[test]
+fn kernel_arg_types_valid() {
+    let pro_que = pro_que();
+    let buffer = pro_que.create_buffer::<f32>().unwrap();
+    let mut kernel = kernel_add(&pro_que);
+
+    kernel.set_arg::<f32>(0, KernelArg::Mem(&buffer)).unwrap();
+    kernel.set_arg::<f32>(0, KernelArg::MemNull).unwrap();
+    kernel.set_arg::<f32>(1, KernelArg::Scalar(1.0)).unwrap();
+    kernel.set_arg::<f32>(2, KernelArg::Local(&64)).unwrap();
+}
+
+#[test]
+fn kernel_arg_types_mismatched() {
+    let pro_que = pro_que();
+    let buffer_u32 = pro_que.create_buffer::<u32>().unwrap();
+    let buffer_f32 = pro_que.create_buffer::<f32>().unwrap();
+    let mut kernel = kernel_add(&pro_que);
+
+    // Buffer element type:
+    assert!(kernel.set_arg::<u32>(0, KernelArg::Mem(&buffer_u32)).is_err());
+    // Buffer bound to a value:
+    assert!(kernel.set_arg::<f32>(1, KernelArg::Mem(&buffer_f32)).is_err());
+    // Value bound to a pointer:
+    assert!(kernel.set_arg::<f32>(0, KernelArg::Scalar(1.0)).is_err());
+    // Local allocation bound to a global pointer:
+    assert!(kernel.set_arg::<f32>(0, KernelArg::Local(&64)).is_err());
+
+    let buffer_u64 = pro_que.create_buffer::<u64>().unwrap();
+    let mut kernel = pro_que.create_kernel("add_ulong").unwrap()
+        .arg_buf(&buffer_u64)
+        .arg_scl(0u64);
+    assert!(kernel.set_arg::<u32>(1, KernelArg::Scalar(1)).is_err());
+    kernel.set_arg::<u64>(1, KernelArg::Scalar(1)).unwrap();
+}
+
+#[test]
+fn kernel_arg_by_name() {
+    let pro_que = pro_que();
+    let buffer = pro_que.create_buffer::<f32>().unwrap();
+    buffer.cmd().fill(1.0, None).enq().unwrap();
+
+    // No arguments need to be declared beforehand:
+    let mut kernel = Kernel::new("add", pro_que.program(), pro_que.queue().clone()).unwrap()
+        .gws(*pro_que.dims())
+        .lws([64]);
+
+    assert_eq!(kernel.arg_idx_by_name("addend").unwrap(), 1);
+    let arg_name = String::from("buffer");
+    kernel.set_arg_by_name(&arg_name, &buffer).unwrap();
+    kernel.set_arg_by_name("addend", KernelArg::Scalar(5.0f32)).unwrap();
+    kernel.set_arg_by_name("scratch", KernelArg::<f32>::Local(&64)).unwrap();
+    kernel.enq().unwrap();
+
+    let mut vec = vec![0.0f32; buffer.len()];
+    buffer.read(&mut vec).enq().unwrap();
+    for &ele in vec.iter() {
+        assert_eq!(ele, 6.0);
+    }
+
+    // Unknown names list the real parameters:
+    let err = kernel.set_arg_by_name("dst", &buffer).err().unwrap();
+    assert!(format!("{}", err).contains("buffer, addend, scratch"));
+}
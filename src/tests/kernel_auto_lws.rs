@@ -0,0 +1,76 @@
+//! Tests automatic local work size selection and global work size padding.
+
+use standard::ProQue;
+
+static SRC: &'static str = r#"
+    __kernel void add(__global uint* buffer, uint addend, uint len) {
+        if (get_global_id(0) < len) {
+            buffer[get_global_id(0)] += addend;
+        }
+    }
+
+    __kernel void add_2d(__global uint* buffer, uint addend, uint2 dims) {
+        uint x = get_global_id(0);
+        uint y = get_global_id(1);
+
+        if (x < dims.x && y < dims.y) {
+            buffer[y * dims.x + x] += addend;
+        }
+    }
+"#;
+
+#[test]
+fn kernel_auto_lws() {
+    // An awkward (prime) length:
+    const LEN: usize = 1009;
+
+    let pro_que = ProQue::builder()
+        .src(SRC)
+        .dims([LEN])
+        .build().unwrap();
+
+    let buffer = pro_que.create_buffer::<u32>().unwrap();
+
+    let kernel = pro_que.create_kernel("add").unwrap()
+        .auto_lws()
+        .arg_buf(&buffer)
+        .arg_scl(1u32)
+        .arg_scl(LEN as u32);
+
+    // Evenly divides the (unpadded) global work size:
+    kernel.enq().unwrap();
+
+    // Padded to a multiple of the chosen local work size:
+    kernel.cmd().pad_gws(2).enq().unwrap();
+
+    // Padded with an explicit local work size:
+    kernel.cmd().lws([16]).pad_gws(2).enq().unwrap();
+
+    let mut vec = vec![0u32; LEN];
+    buffer.read(&mut vec).enq().unwrap();
+    assert!(vec.iter().all(|&val| val == 3));
+}
+
+#[test]
+fn kernel_pad_gws_2d() {
+    const DIMS: [usize; 2] = [67, 33];
+
+    let pro_que = ProQue::builder()
+        .src(SRC)
+        .dims(DIMS)
+        .build().unwrap();
+
+    let buffer = pro_que.create_buffer::<u32>().unwrap();
+
+    let kernel = pro_que.create_kernel("add_2d").unwrap()
+        .pad_gws(2)
+        .arg_buf(&buffer)
+        .arg_scl(5u32)
+        .arg_unset();
+
+    kernel.enq().unwrap();
+
+    let mut vec = vec![0u32; DIMS[0] * DIMS[1]];
+    buffer.read(&mut vec).enq().unwrap();
+    assert!(vec.iter().all(|&val| val == 5));
+}
@@ -0,0 +1,73 @@
+//! Tests `KernelBuilder`.
+
+use standard::{ProQue, Kernel, Program};
+
+static SRC: &'static str = r#"
+    __kernel void add(__global float* buffer, float addend) {
+        buffer[get_global_id(0)] += addend;
+    }
+"#;
+
+#[test]
+fn kernel_builder() {
+    let pro_que = ProQue::builder()
+        .src(SRC)
+        .dims([1024])
+        .build().unwrap();
+
+    let buffer = pro_que.create_buffer::<f32>().unwrap();
+
+    let mut kernel = pro_que.kernel_builder("add")
+        .arg_buf(&buffer)
+        .arg_scl_named::<f32>("addend", None)
+        .build().unwrap();
+
+    kernel.set_arg_scl_named("addend", 3.0f32).unwrap();
+    kernel.enq().unwrap();
+
+    let mut vec = vec![0.0f32; buffer.len()];
+    buffer.read(&mut vec).enq().unwrap();
+    for &ele in vec.iter() {
+        assert_eq!(ele, 3.0);
+    }
+
+    // Unregistered names are an error:
+    assert!(kernel.set_arg_scl_named("adend", 3.0f32).is_err());
+}
+
+#[test]
+fn kernel_builder_errors() {
+    let pro_que = ProQue::builder()
+        .src(SRC)
+        .dims([1024])
+        .build().unwrap();
+
+    let buffer = pro_que.create_buffer::<f32>().unwrap();
+
+    // Too many arguments:
+    assert!(pro_que.kernel_builder("add")
+        .arg_buf(&buffer)
+        .arg_scl(1.0f32)
+        .arg_scl(1.0f32)
+        .build().is_err());
+
+    // Too many arguments, with argument types available:
+    let pro_que_arg_info = ProQue::builder()
+        .prog_bldr(Program::builder().src(SRC).cmplr_opt("-cl-kernel-arg-info"))
+        .dims([1024])
+        .build().unwrap();
+
+    let buffer_arg_info = pro_que_arg_info.create_buffer::<f32>().unwrap();
+
+    assert!(pro_que_arg_info.kernel_builder("add")
+        .arg_buf(&buffer_arg_info)
+        .arg_scl(1.0f32)
+        .arg_scl(1.0f32)
+        .build().is_err());
+
+    // Nonexistent kernel:
+    assert!(pro_que.kernel_builder("sub").build().is_err());
+
+    // Missing program and queue:
+    assert!(Kernel::builder().name("add").build().is_err());
+}
@@ -0,0 +1,43 @@
+//! Tests splitting kernel launches into chunks.
+
+use standard::{ProQue, Event};
+
+static SRC: &'static str = r#"
+    __kernel void write_ids(__global uint* buffer) {
+        uint idx = get_global_id(1) * 64 + get_global_id(0);
+        buffer[idx] += idx;
+    }
+"#;
+
+#[test]
+fn kernel_chunked() {
+    let pro_que = ProQue::builder()
+        .src(SRC)
+        .dims([64, 100])
+        .build().unwrap();
+
+    let buffer = pro_que.create_buffer::<u32>().unwrap();
+
+    let kernel = pro_que.create_kernel("write_ids").unwrap()
+        .arg_buf(&buffer);
+
+    // 64 * 100 work items in chunks of at most 64 * 16 (with a remainder):
+    let mut event = Event::empty();
+    kernel.cmd().lws([16, 4]).chunked(64 * 16).enew(&mut event).enq().unwrap();
+    event.wait().unwrap();
+
+    let mut vec = vec![0u32; buffer.len()];
+    buffer.read(&mut vec).enq().unwrap();
+
+    for (idx, &val) in vec.iter().enumerate() {
+        assert_eq!(val, idx as u32);
+    }
+
+    // Chunks smaller than a row of work groups are rounded up:
+    kernel.cmd().lws([16, 4]).chunked(1).enq().unwrap();
+    buffer.read(&mut vec).enq().unwrap();
+
+    for (idx, &val) in vec.iter().enumerate() {
+        assert_eq!(val, idx as u32 * 2);
+    }
+}
@@ -0,0 +1,93 @@
+//! Tests `Kernel::duplicate` and `KernelPool`.
+
+use std::sync::Arc;
+use std::thread;
+use standard::{ProQue, KernelPool};
+
+static SRC: &'static str = r#"
+    __kernel void set(__global uint* buffer, uint offset, uint value) {
+        buffer[get_global_id(0) + offset] = value;
+    }
+"#;
+
+const CHUNK_LEN: usize = 256;
+const CHUNK_COUNT: usize = 4;
+
+#[test]
+fn kernel_duplicate() {
+    let pro_que = ProQue::builder()
+        .src(SRC)
+        .dims([CHUNK_LEN * CHUNK_COUNT])
+        .build().unwrap();
+
+    let buffer = pro_que.create_buffer::<u32>().unwrap();
+
+    let mut kernel = pro_que.create_kernel("set").unwrap()
+        .gws([CHUNK_LEN])
+        .arg_buf(&buffer)
+        .arg_scl_named("offset", Some(0u32))
+        .arg_scl(7u32);
+
+    // The duplicate keeps every argument, including the named one:
+    let mut duplicate = kernel.duplicate().unwrap();
+    assert_eq!(duplicate.arg_count(), kernel.arg_count());
+    assert_eq!(duplicate.named_arg_idx("offset"), Some(1));
+    duplicate.set_arg_scl_named("offset", CHUNK_LEN as u32).unwrap();
+
+    // Changes to one do not affect the other:
+    kernel.enq().unwrap();
+    duplicate.enq().unwrap();
+
+    let mut vec = vec![0u32; buffer.len()];
+    buffer.read(&mut vec).enq().unwrap();
+
+    for (idx, &val) in vec.iter().enumerate() {
+        let expected = if idx < CHUNK_LEN * 2 { 7 } else { 0 };
+        assert_eq!(val, expected, "vec[{}]", idx);
+    }
+}
+
+#[test]
+fn kernel_pool() {
+    let pro_que = ProQue::builder()
+        .src(SRC)
+        .dims([CHUNK_LEN * CHUNK_COUNT])
+        .build().unwrap();
+
+    let buffer = pro_que.create_buffer::<u32>().unwrap();
+
+    let kernel = pro_que.create_kernel("set").unwrap()
+        .gws([CHUNK_LEN])
+        .arg_buf(&buffer)
+        .arg_scl_named("offset", Some(0u32))
+        .arg_scl_named("value", Some(0u32));
+
+    let pool = Arc::new(KernelPool::with_count(kernel, 2).unwrap());
+    assert_eq!(pool.idle_count(), 2);
+
+    let threads: Vec<_> = (0..CHUNK_COUNT).map(|chunk_idx| {
+        let pool = pool.clone();
+
+        thread::spawn(move || {
+            let mut kernel = pool.get().unwrap();
+            kernel.set_arg_scl_named("offset", (chunk_idx * CHUNK_LEN) as u32).unwrap();
+            kernel.set_arg_scl_named("value", chunk_idx as u32 + 1).unwrap();
+            kernel.enq().unwrap();
+            kernel.default_queue().finish();
+        })
+    }).collect();
+
+    for thread in threads {
+        thread.join().unwrap();
+    }
+
+    // Every kernel has been returned:
+    assert!(pool.idle_count() >= 2);
+
+    let mut vec = vec![0u32; buffer.len()];
+    buffer.read(&mut vec).enq().unwrap();
+
+    for (idx, &val) in vec.iter().enumerate() {
+        assert_eq!(val, (idx / CHUNK_LEN) as u32 + 1, "vec[{}]", idx);
+    }
+}
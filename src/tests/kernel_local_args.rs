@@ -0,0 +1,63 @@
+//! Tests resizing local memory kernel arguments.
+
+use core::{DeviceInfo, DeviceInfoResult};
+use standard::ProQue;
+
+static SRC: &'static str = r#"
+    __kernel void sum_groups(__local float* scratch, __global float* result) {
+        uint lid = get_local_id(0);
+        scratch[lid] = 1.0f;
+        barrier(CLK_LOCAL_MEM_FENCE);
+
+        if (lid == 0) {
+            float sum = 0.0f;
+            for (uint i = 0; i < get_local_size(0); i++) {
+                sum += scratch[i];
+            }
+            result[get_group_id(0)] = sum;
+        }
+    }
+"#;
+
+#[test]
+fn kernel_local_args() {
+    let pro_que = ProQue::builder()
+        .src(SRC)
+        .dims(256)
+        .build().unwrap();
+
+    let result = pro_que.create_buffer::<f32>().unwrap();
+
+    let mut kernel = pro_que.create_kernel("sum_groups").unwrap()
+        .arg_loc_named::<f32>("scratch", 16)
+        .arg_buf(&result);
+
+    kernel.cmd().lws([16]).enq().unwrap();
+
+    let mut vec = vec![0.0f32; result.len()];
+    result.read(&mut vec).enq().unwrap();
+    assert!(vec[..256 / 16].iter().all(|&sum| sum == 16.0));
+
+    // Resize (by name and by index) and run with larger work groups:
+    kernel.set_arg_loc_named::<f32>("scratch", 32).unwrap();
+    kernel.cmd().lws([32]).enq().unwrap();
+    result.read(&mut vec).enq().unwrap();
+    assert!(vec[..256 / 32].iter().all(|&sum| sum == 32.0));
+
+    kernel.set_arg_loc::<f32>(0, 64).unwrap();
+    kernel.cmd().lws([64]).enq().unwrap();
+    result.read(&mut vec).enq().unwrap();
+    assert!(vec[..256 / 64].iter().all(|&sum| sum == 64.0));
+
+    // Exceeding the device's local memory is an error and leaves the
+    // argument unchanged:
+    let local_mem_size = match pro_que.queue().device().info(DeviceInfo::LocalMemSize) {
+        DeviceInfoResult::LocalMemSize(s) => s as usize,
+        res => panic!("Unexpected device info result: {:?}", res),
+    };
+
+    assert!(kernel.set_arg_loc::<f32>(0, local_mem_size / 4 + 1).is_err());
+    assert!(kernel.set_arg_loc_named::<f32>("scratch", local_mem_size).is_err());
+    assert!(kernel.set_arg_loc_named::<f32>("nonexistent", 16).is_err());
+    assert_eq!(kernel.args()[0].mem_len, Some(64));
+}
@@ -0,0 +1,74 @@
+//! Tests setting sampler arguments by index and by name.
+
+use flags;
+use standard::{ProQue, Image, Sampler};
+use enums::{AddressingMode, FilterMode, ImageChannelOrder, ImageChannelDataType, MemObjectType};
+
+static SRC: &'static str = r#"
+    __kernel void copy(
+                sampler_t sampler_host,
+                read_only image2d_t img_src,
+                write_only image2d_t img_dst)
+    {
+        int2 coord = (int2)(get_global_id(0), get_global_id(1));
+        write_imagef(img_dst, coord, read_imagef(img_src, sampler_host, coord));
+    }
+"#;
+
+#[test]
+fn kernel_sampler_args() {
+    let proque = ProQue::builder()
+        .src(SRC)
+        .dims([32, 32])
+        .build().unwrap();
+
+    let vec = vec![0.5f32; proque.dims().to_len() * 4];
+
+    let img_src = Image::<f32>::builder()
+        .channel_order(ImageChannelOrder::Rgba)
+        .channel_data_type(ImageChannelDataType::Float)
+        .image_type(MemObjectType::Image2d)
+        .dims(proque.dims())
+        .flags(flags::MEM_READ_ONLY | flags::MEM_COPY_HOST_PTR)
+        .build_with_data(proque.queue().clone(), &vec).unwrap();
+    let img_dst = Image::<f32>::builder()
+        .channel_order(ImageChannelOrder::Rgba)
+        .channel_data_type(ImageChannelDataType::Float)
+        .image_type(MemObjectType::Image2d)
+        .dims(proque.dims())
+        .flags(flags::MEM_WRITE_ONLY)
+        .build(proque.queue().clone()).unwrap();
+
+    let sampler_a = Sampler::new(proque.context(), false, AddressingMode::None,
+        FilterMode::Nearest).unwrap();
+
+    let mut kernel = proque.create_kernel("copy").unwrap()
+        .arg_smp_named("sampler_host", Some(&sampler_a))
+        .arg_img(&img_src)
+        .arg_img(&img_dst);
+
+    // Replace the sampler by name and drop our handle. The kernel must keep
+    // its own reference alive:
+    {
+        let sampler_b = Sampler::new(proque.context(), false, AddressingMode::ClampToEdge,
+            FilterMode::Nearest).unwrap();
+        kernel.set_arg_smp_named("sampler_host", Some(&sampler_b)).unwrap();
+    }
+    kernel.enq().unwrap();
+
+    // Unregistered names are an error:
+    assert!(kernel.set_arg_smp_named("not_a_sampler", Some(&sampler_a)).is_err());
+
+    // By index:
+    {
+        let sampler_c = Sampler::new(proque.context(), false, AddressingMode::Clamp,
+            FilterMode::Nearest).unwrap();
+        kernel.set_arg_smp(0, &sampler_c).unwrap();
+    }
+    kernel.enq().unwrap();
+    assert!(kernel.set_arg_smp(3, &sampler_a).is_err());
+
+    let mut result = vec![0.0f32; vec.len()];
+    img_dst.read(&mut result).enq().unwrap();
+    assert_eq!(vec, result);
+}
@@ -0,0 +1,63 @@
+//! Tests the kernel auto-tuner and its result cache.
+
+use std::env;
+use std::fs;
+use standard::{ProQue, Program, SpatialDims};
+use tune::Tuner;
+
+static SRC: &'static str = r#"
+    __kernel void add(__global float* buffer, float addend) {
+        buffer[get_global_id(0)] += addend * SCALE;
+    }
+"#;
+
+#[test]
+fn kernel_tune() {
+    let cache_path = env::temp_dir().join("ocl_test_kernel_tune_cache.txt");
+    let _ = fs::remove_file(&cache_path);
+
+    let pro_que = ProQue::builder()
+        .prog_bldr(Program::builder().src(SRC).cmplr_def("SCALE", 1))
+        .dims([1024])
+        .build().unwrap();
+
+    let buffer = pro_que.create_buffer::<f32>().unwrap();
+
+    let kernel = pro_que.create_kernel("add").unwrap()
+        .arg_buf(&buffer)
+        .arg_scl(1.0f32);
+
+    let tuner = Tuner::new(&kernel)
+        // An invalid candidate (does not divide 1024) is skipped:
+        .lws([48]).lws([16]).lws([32])
+        .template(Program::builder().src(SRC))
+        .define("SCALE", &["1.0f", "2.0f"])
+        .iters(2)
+        .cache_path(&cache_path);
+
+    let tuned = tuner.run().unwrap();
+    assert!(tuned.time_ns.is_some());
+    assert!(tuned.config.lws == SpatialDims::One(16) ||
+        tuned.config.lws == SpatialDims::One(32));
+    assert_eq!(tuned.config.defines.len(), 1);
+    assert_eq!(tuned.kernel.get_lws(), tuned.config.lws);
+
+    // The tuned kernel keeps the original's arguments:
+    tuned.kernel.enq().unwrap();
+
+    // A second run reads the cache:
+    let cached = tuner.run().unwrap();
+    assert!(cached.time_ns.is_none());
+    assert_eq!(cached.config, tuned.config);
+
+    // Missing definitions or candidates are errors:
+    assert!(Tuner::new(&kernel).no_cache().run().is_err());
+    assert!(Tuner::new(&kernel).lws([16]).define("SCALE", &[1]).no_cache().run().is_err());
+
+    // Tuning only the local work size:
+    let tuned = Tuner::new(&kernel).lws([64]).no_cache().run().unwrap();
+    assert_eq!(tuned.config.lws, SpatialDims::One(64));
+    assert!(tuned.config.defines.is_empty());
+
+    let _ = fs::remove_file(&cache_path);
+}
@@ -0,0 +1,101 @@
+//! Tests validation of work sizes and arguments before a kernel is enqueued.
+
+use standard::ProQue;
+
+static SRC: &'static str = r#"
+    __kernel void add(__global float* buffer, float addend) {
+        buffer[get_global_id(0)] += addend;
+    }
+
+    __kernel __attribute__((reqd_work_group_size(16, 1, 1)))
+    void add_reqd(__global float* buffer, float addend) {
+        buffer[get_global_id(0)] += addend;
+    }
+"#;
+
+fn assert_err_names_kernel<T: ::std::fmt::Debug>(result: ::core::error::Result<T>, name: &str) {
+    match result {
+        Ok(val) => panic!("Expected an error, got: {:?}", val),
+        Err(err) => {
+            let msg = err.to_string();
+            assert!(msg.contains(&format!("'{}'", name)), "Error does not name kernel: {}", msg);
+        },
+    }
+}
+
+#[test]
+fn kernel_validation() {
+    let pro_que = ProQue::builder()
+        .src(SRC)
+        .dims([1024])
+        .build().unwrap();
+
+    let buffer = pro_que.create_buffer::<f32>().unwrap();
+
+    let kernel = pro_que.create_kernel("add").unwrap()
+        .arg_buf(&buffer)
+        .arg_scl(1.0f32);
+
+    kernel.enq().unwrap();
+    kernel.cmd().lws([64]).enq().unwrap();
+
+    // Local work size does not divide the global work size:
+    assert_err_names_kernel(kernel.cmd().lws([48]).enq(), "add");
+    // Differing dimension counts:
+    assert_err_names_kernel(kernel.cmd().lws([8, 8]).enq(), "add");
+    assert_err_names_kernel(kernel.cmd().gwo([0, 0]).enq(), "add");
+    // Zero sized local work size:
+    assert_err_names_kernel(kernel.cmd().lws([0]).enq(), "add");
+    // Local work size over the device limit:
+    let max_wg_size = pro_que.queue().device().max_wg_size().unwrap();
+    assert_err_names_kernel(kernel.cmd().gws([max_wg_size * 2]).lws([max_wg_size * 2]).enq(),
+        "add");
+
+    // Unset arguments:
+    let unset = pro_que.create_kernel("add").unwrap()
+        .arg_buf(&buffer)
+        .arg_unset();
+    assert_err_names_kernel(unset.enq(), "add");
+}
+
+#[test]
+fn kernel_validation_missing_trailing_arg() {
+    // Built without '-cl-kernel-arg-info' so argument types are unavailable:
+    let pro_que = ProQue::builder()
+        .src(SRC)
+        .dims([1024])
+        .build().unwrap();
+
+    let buffer = pro_que.create_buffer::<f32>().unwrap();
+
+    // The last argument is never declared:
+    let missing = pro_que.create_kernel("add").unwrap()
+        .arg_buf(&buffer);
+    assert_err_names_kernel(missing.enq(), "add");
+
+    let mut vec = vec![0.0f32; buffer.len()];
+    buffer.read(&mut vec).enq().unwrap();
+    assert!(vec.iter().all(|&val| val == 0.0));
+}
+
+#[test]
+fn kernel_validation_reqd_wg_size() {
+    let pro_que = ProQue::builder()
+        .src(SRC)
+        .dims([1024])
+        .build().unwrap();
+
+    let buffer = pro_que.create_buffer::<f32>().unwrap();
+
+    let kernel = pro_que.create_kernel("add_reqd").unwrap()
+        .arg_buf(&buffer)
+        .arg_scl(1.0f32);
+
+    // No local work size, or the wrong one:
+    assert_err_names_kernel(kernel.enq(), "add_reqd");
+    assert_err_names_kernel(kernel.cmd().lws([32]).enq(), "add_reqd");
+
+    kernel.cmd().lws([16]).enq().unwrap();
+    // The required size is chosen automatically:
+    kernel.cmd().auto_lws().enq().unwrap();
+}
@@ -11,12 +11,35 @@ pub mod build_error;
 pub mod buffer_copy;
 pub mod buffer_ops_rect;
 pub mod image_ops;
+pub mod image_layers;
+pub mod pixel_conversion;
 pub mod buffer_fill;
 pub mod clear_completed;
 pub mod concurrent;
 pub mod kernel_arg_ptr;
+pub mod kernel_arg_types;
+pub mod kernel_builder;
+pub mod kernel_sampler_args;
+pub mod kernel_duplicate;
+pub mod typed_kernel;
+pub mod kernel_auto_lws;
+pub mod kernel_validation;
+pub mod kernel_tune;
+pub mod kernel_arg_desc;
+pub mod host_fn;
+pub mod kernel_chunked;
+pub mod resource_tracking;
+pub mod kernel_local_args;
+pub mod user_event;
+pub mod event_callbacks;
+#[cfg(feature = "future")] pub mod event_future;
+pub mod event_profiling;
+pub mod timeline;
+pub mod event_list;
 pub mod vector_types;
 pub mod context_props;
+pub mod sampler_reference;
+pub mod sampler_builder;
 
 use rand::{self, Rng};
 use core::error::{Error as OclError, Result as OclResult};
@@ -0,0 +1,108 @@
+//! Tests `PixelConverter` and float image reads and writes.
+
+use core::{ImageFormat, ImageChannelOrder, ImageChannelDataType};
+use standard::{ProQue, Image, PixelConverter};
+use enums::MemObjectType;
+
+fn converter(order: ImageChannelOrder, data_type: ImageChannelDataType) -> PixelConverter {
+    PixelConverter::new(ImageFormat::new(order, data_type)).unwrap()
+}
+
+#[test]
+fn pack_normalized() {
+    let conv = converter(ImageChannelOrder::Rgba, ImageChannelDataType::UnormInt8);
+    assert_eq!(conv.pixel_size(), 4);
+    // Rounds to nearest even, saturates, and converts NaN to zero:
+    assert_eq!(conv.pack(&[0.5, 1.5, -1.0, ::std::f32::NAN]).unwrap(), vec![128, 255, 0, 0]);
+
+    let conv = converter(ImageChannelOrder::Bgra, ImageChannelDataType::SnormInt8);
+    assert_eq!(conv.pack(&[1.0, -2.0, 0.5, 0.0]).unwrap(), vec![64, 128, 127, 0]);
+    // -128 unpacks to -1.0 (clamped), not -128/127:
+    assert_eq!(conv.unpack(&[64, 128, 127, 0]).unwrap(), vec![1.0, -1.0, 64.0 / 127.0, 0.0]);
+}
+
+#[test]
+fn pack_half_float() {
+    let conv = converter(ImageChannelOrder::R, ImageChannelDataType::HalfFloat);
+    let vals = [1.0f32, -2.0, 0.5, 65504.0, 1.0e6, 5.9604645e-8];
+    let halves = [0x3c00u16, 0xc000, 0x3800, 0x7bff, 0x7c00, 0x0001];
+
+    for (&val, &half) in vals.iter().zip(halves.iter()) {
+        let bytes = conv.pack(&[val, 0.0, 0.0, 0.0]).unwrap();
+        assert_eq!(bytes.len(), 2);
+        let packed = bytes[0] as u16 | (bytes[1] as u16) << 8;
+        if cfg!(target_endian = "little") { assert_eq!(packed, half); }
+
+        let unpacked = conv.unpack(&bytes).unwrap();
+        assert_eq!(unpacked[1..], [0.0, 0.0, 1.0]);
+        if half != 0x7c00 { assert_eq!(unpacked[0], val); }
+    }
+}
+
+#[test]
+fn pack_packed_formats() {
+    let conv = converter(ImageChannelOrder::Rgb, ImageChannelDataType::UnormShort565);
+    assert_eq!(conv.pixel_size(), 2);
+    let bytes = conv.pack(&[1.0, 0.0, 1.0, 0.5]).unwrap();
+    assert_eq!(conv.unpack(&bytes).unwrap(), vec![1.0, 0.0, 1.0, 1.0]);
+
+    let conv = converter(ImageChannelOrder::Rgbx, ImageChannelDataType::UnormInt101010);
+    assert_eq!(conv.pixel_size(), 4);
+    let bytes = conv.pack(&[0.0, 1.0, 0.0, 0.0]).unwrap();
+    assert_eq!(conv.unpack(&bytes).unwrap(), vec![0.0, 1.0, 0.0, 1.0]);
+
+    assert!(PixelConverter::new(ImageFormat::new(ImageChannelOrder::Rgba,
+        ImageChannelDataType::UnormShort565)).is_err());
+    assert!(PixelConverter::new(ImageFormat::new(ImageChannelOrder::Rgb,
+        ImageChannelDataType::UnormInt8)).is_err());
+}
+
+#[test]
+fn unpack_channel_orders() {
+    let conv = converter(ImageChannelOrder::Luminance, ImageChannelDataType::UnormInt8);
+    assert_eq!(conv.unpack(&[255]).unwrap(), vec![1.0, 1.0, 1.0, 1.0]);
+
+    let conv = converter(ImageChannelOrder::Intensity, ImageChannelDataType::UnsignedInt8);
+    assert_eq!(conv.unpack(&[7]).unwrap(), vec![7.0; 4]);
+
+    let conv = converter(ImageChannelOrder::A, ImageChannelDataType::UnormInt8);
+    assert_eq!(conv.unpack(&[0]).unwrap(), vec![0.0; 4]);
+
+    let conv = converter(ImageChannelOrder::Rx, ImageChannelDataType::SignedInt8);
+    assert_eq!(conv.pack(&[-3.0, 9.0, 9.0, 9.0]).unwrap(), vec![253, 0]);
+
+    assert!(conv.pack(&[0.0; 3]).is_err());
+    assert!(conv.unpack(&[0; 3]).is_err());
+}
+
+#[test]
+fn image_read_write_f32() {
+    let dims = [16, 8];
+    let proque = ProQue::builder()
+        .src("__kernel void nop() {}")
+        .dims(dims)
+        .build().unwrap();
+
+    let img = Image::<u8>::builder()
+        .channel_order(ImageChannelOrder::Rgba)
+        .channel_data_type(ImageChannelDataType::UnormInt8)
+        .image_type(MemObjectType::Image2d)
+        .dims(dims)
+        .build(proque.queue().clone()).unwrap();
+
+    let src: Vec<f32> = (0..img.pixel_count() * 4).map(|i| (i % 256) as f32 / 255.0).collect();
+    img.write_f32(&src).enq().unwrap();
+
+    let mut raw = vec![0u8; img.element_count()];
+    img.read(&mut raw).enq().unwrap();
+    for (i, &ele) in raw.iter().enumerate() {
+        assert_eq!(ele as usize, i % 256);
+    }
+
+    let mut dst = vec![0.0f32; src.len()];
+    img.read_f32(&mut dst).enq().unwrap();
+    assert_eq!(dst, src);
+
+    // Wrong length:
+    assert!(img.write_f32(&src[..4]).enq().is_err());
+}
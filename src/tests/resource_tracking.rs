@@ -0,0 +1,54 @@
+//! Tests that the objects used by enqueued commands are retained until the
+//! commands complete.
+
+use standard::{ProQue, Event, Buffer, UserEvent};
+
+static SRC: &'static str = r#"
+    __kernel void add(__global float* buffer, float addend) {
+        buffer[get_global_id(0)] += addend;
+    }
+"#;
+
+#[test]
+fn resource_tracking() {
+    let pro_que = ProQue::builder()
+        .src(SRC)
+        .dims([1 << 20])
+        .build().unwrap();
+
+    let queue = pro_que.queue().clone();
+    let result = pro_que.create_buffer::<f32>().unwrap();
+
+    // Holds back each command until set:
+    let gate = UserEvent::new(pro_que.context()).unwrap();
+
+    // The buffer and kernel are dropped while commands using them are still
+    // in flight:
+    {
+        let buffer: Buffer<f32> = pro_que.create_buffer().unwrap();
+        let kernel = pro_que.create_kernel("add").unwrap()
+            .arg_buf(&buffer)
+            .arg_scl(1.0f32);
+
+        for _ in 0..8 {
+            kernel.cmd().ewait(gate.as_event()).enq().unwrap();
+        }
+
+        buffer.cmd().copy(&result, None, None).ewait(gate.as_event()).enq().unwrap();
+    }
+
+    assert_eq!(queue.tracker().pending_count(), 9);
+    gate.set_complete().unwrap();
+
+    // Events requested for tracked commands work as usual:
+    let mut event = Event::empty();
+    result.cmd().fill(0.0f32, None).enew(&mut event).enq().unwrap();
+    event.wait().unwrap();
+
+    queue.finish();
+    assert_eq!(queue.tracker().pending_count(), 0);
+
+    let mut vec = vec![1.0f32; result.len()];
+    result.read(&mut vec).enq().unwrap();
+    assert!(vec.iter().all(|&val| val == 0.0));
+}
@@ -0,0 +1,46 @@
+//! Tests `SamplerBuilder` and `Sampler::properties`.
+
+use standard::{Context, Sampler};
+use enums::{AddressingMode, FilterMode, SamplerInfo, SamplerInfoResult};
+
+#[test]
+fn sampler_builder_properties() {
+    let context = Context::builder().build().unwrap();
+
+    let sampler = Sampler::builder()
+        .normalize_coords(true)
+        .addressing_mode(AddressingMode::Repeat)
+        .filter_mode(FilterMode::Linear)
+        .build(&context).unwrap();
+
+    let props = *sampler.properties();
+    assert!(props.normalize_coords);
+    assert_eq!(props.addressing_mode, AddressingMode::Repeat);
+    assert_eq!(props.filter_mode, FilterMode::Linear);
+    assert_eq!(props.mip_filter_mode, None);
+
+    // Cached properties should agree with the device:
+    match sampler.info(SamplerInfo::NormalizedCoords) {
+        SamplerInfoResult::NormalizedCoords(nc) => assert_eq!(nc, props.normalize_coords),
+        res => panic!("Unexpected info result: {:?}", res),
+    }
+    match sampler.info(SamplerInfo::AddressingMode) {
+        SamplerInfoResult::AddressingMode(am) => assert_eq!(am, props.addressing_mode),
+        res => panic!("Unexpected info result: {:?}", res),
+    }
+    match sampler.info(SamplerInfo::FilterMode) {
+        SamplerInfoResult::FilterMode(fm) => assert_eq!(fm, props.filter_mode),
+        res => panic!("Unexpected info result: {:?}", res),
+    }
+
+    let host_sampler = sampler.to_host_sampler();
+    assert_eq!(host_sampler.addressing_mode(), AddressingMode::Repeat);
+}
+
+#[cfg(not(feature = "opencl_version_2_0"))]
+#[test]
+fn sampler_builder_mip_requires_2_0() {
+    let context = Context::builder().build().unwrap();
+    assert!(Sampler::builder().mip_filter_mode(FilterMode::Linear).build(&context).is_err());
+    assert!(Sampler::builder().lod_min(0.0).lod_max(4.0).build(&context).is_err());
+}
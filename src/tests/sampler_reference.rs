@@ -0,0 +1,85 @@
+//! Tests the host-side reference sampler against known results.
+//!
+//! These tests do not require a device.
+
+use reference::HostSampler;
+use enums::{AddressingMode, FilterMode};
+
+/// A 1d image, four pixels wide, where every component of pixel `x` is `x + 1`.
+fn pixels_1d() -> Vec<f32> {
+    (0..4).flat_map(|x| vec![(x + 1) as f32; 4]).collect()
+}
+
+fn sample_1d(sampler: HostSampler, s: f32) -> f32 {
+    sampler.sample(&pixels_1d(), [4], [s, 0.0, 0.0]).unwrap()[0]
+}
+
+#[test]
+fn nearest_unnormalized() {
+    let cte = HostSampler::new(false, AddressingMode::ClampToEdge, FilterMode::Nearest);
+    assert_eq!(sample_1d(cte, 1.5), 2.0);
+    assert_eq!(sample_1d(cte, -3.0), 1.0);
+    assert_eq!(sample_1d(cte, 10.0), 4.0);
+
+    let clamp = HostSampler::new(false, AddressingMode::Clamp, FilterMode::Nearest);
+    assert_eq!(sample_1d(clamp, -0.5), 0.0);
+    assert_eq!(sample_1d(clamp, 4.0), 0.0);
+    assert_eq!(sample_1d(clamp, 3.9), 4.0);
+
+    let none = HostSampler::new(false, AddressingMode::None, FilterMode::Nearest);
+    assert_eq!(sample_1d(none, 2.2), 3.0);
+    assert!(none.sample(&pixels_1d(), [4], [4.5, 0.0, 0.0]).is_err());
+}
+
+#[test]
+fn nearest_normalized_repeat() {
+    let repeat = HostSampler::new(true, AddressingMode::Repeat, FilterMode::Nearest);
+    assert_eq!(sample_1d(repeat, 1.3), 2.0);
+    assert_eq!(sample_1d(repeat, -0.1), 4.0);
+
+    let mirrored = HostSampler::new(true, AddressingMode::MirroredRepeat, FilterMode::Nearest);
+    assert_eq!(sample_1d(mirrored, 1.3), 3.0);
+    assert_eq!(sample_1d(mirrored, -0.3), 2.0);
+}
+
+#[test]
+fn linear() {
+    let cte = HostSampler::new(false, AddressingMode::ClampToEdge, FilterMode::Linear);
+    assert_eq!(sample_1d(cte, 2.0), 2.5);
+    assert_eq!(sample_1d(cte, 0.5), 1.0);
+    assert_eq!(sample_1d(cte, 0.25), 1.0);
+
+    let clamp = HostSampler::new(false, AddressingMode::Clamp, FilterMode::Linear);
+    assert_eq!(sample_1d(clamp, 0.25), 0.75);
+
+    let repeat = HostSampler::new(true, AddressingMode::Repeat, FilterMode::Linear);
+    assert_eq!(sample_1d(repeat, 0.0), 2.5);
+}
+
+#[test]
+fn linear_2d() {
+    let pixels: Vec<f32> = (0..4).flat_map(|p| vec![(p + 1) as f32; 4]).collect();
+    let cte = HostSampler::new(false, AddressingMode::ClampToEdge, FilterMode::Linear);
+    assert_eq!(cte.sample(&pixels, [2, 2], [1.0, 1.0, 0.0]).unwrap(), [2.5; 4]);
+    assert_eq!(cte.sample(&pixels, [2, 2], [1.5, 0.5, 0.0]).unwrap(), [2.0; 4]);
+}
+
+#[test]
+fn sample_int() {
+    let pixels: Vec<i32> = (0..4).flat_map(|x| vec![x * 10; 4]).collect();
+    let nearest = HostSampler::new(false, AddressingMode::Clamp, FilterMode::Nearest);
+    assert_eq!(nearest.sample_int(&pixels, [4], [2.5, 0.0, 0.0]).unwrap(), [20; 4]);
+    assert_eq!(nearest.sample_int(&pixels, [4], [-1.0, 0.0, 0.0]).unwrap(), [0; 4]);
+
+    let linear = HostSampler::new(false, AddressingMode::Clamp, FilterMode::Linear);
+    assert!(linear.sample_int(&pixels, [4], [2.5, 0.0, 0.0]).is_err());
+}
+
+#[test]
+fn invalid_settings() {
+    let repeat = HostSampler::new(false, AddressingMode::Repeat, FilterMode::Nearest);
+    assert!(repeat.sample(&pixels_1d(), [4], [1.0, 0.0, 0.0]).is_err());
+
+    let cte = HostSampler::new(false, AddressingMode::ClampToEdge, FilterMode::Nearest);
+    assert!(cte.sample(&pixels_1d(), [5], [1.0, 0.0, 0.0]).is_err());
+}
@@ -0,0 +1,75 @@
+//! Tests recording command timelines and exporting them as Chrome traces.
+
+use std::env;
+use std::fs::{self, File};
+use std::io::Read;
+use core::QUEUE_PROFILING_ENABLE;
+use standard::{ProQue, Queue, EventList, Timeline};
+
+static SRC: &'static str = r#"
+    __kernel void add(__global float* buffer, float addend) {
+        buffer[get_global_id(0)] += addend;
+    }
+"#;
+
+#[test]
+fn timeline() {
+    let pro_que = ProQue::builder()
+        .src(SRC)
+        .dims([4096])
+        .queue_properties(QUEUE_PROFILING_ENABLE)
+        .build().unwrap();
+
+    // A second queue on the same device for transfers:
+    let transfer_queue = Queue::new(pro_que.context(), pro_que.queue().device().clone(),
+        Some(QUEUE_PROFILING_ENABLE)).unwrap();
+
+    let timeline = Timeline::new();
+    pro_que.queue().set_timeline(Some(&timeline));
+    transfer_queue.set_timeline(Some(&timeline));
+
+    let mut buffer = pro_que.create_buffer::<f32>().unwrap();
+
+    let kernel = pro_que.create_kernel("add").unwrap()
+        .arg_buf(&buffer)
+        .arg_scl(1.0f32);
+
+    let mut vec = vec![0.0f32; buffer.len()];
+    let mut events = EventList::new();
+
+    for _ in 0..4 {
+        kernel.cmd().enew(&mut events).enq().unwrap();
+    }
+
+    buffer.set_default_queue(&transfer_queue);
+    buffer.read(&mut vec).ewait(&events).enq().unwrap();
+
+    timeline.wait("wait for kernels", &events).unwrap();
+    pro_que.queue().finish();
+    assert_eq!(timeline.command_count(), 5);
+
+    // Commands enqueued after detaching are not recorded:
+    pro_que.queue().set_timeline(None);
+    kernel.enq().unwrap();
+    assert_eq!(timeline.command_count(), 5);
+
+    let trace = timeline.to_chrome_trace().unwrap();
+    assert!(trace.starts_with("{\"traceEvents\":["));
+    assert_eq!(trace.matches("\"name\":\"add\"").count(), 4);
+    assert_eq!(trace.matches("\"kernel\":\"add\"").count(), 4);
+    assert_eq!(trace.matches("\"cat\":\"command\"").count(), 5);
+    assert!(trace.contains("\"name\":\"wait for kernels\""));
+    assert!(trace.contains("\"name\":\"Queue::finish\""));
+    assert!(trace.contains("Queue 0 ("));
+    assert!(trace.contains("Queue 1 ("));
+
+    let path = env::temp_dir().join("ocl_test_timeline.json");
+    timeline.write_chrome_trace(&path).unwrap();
+    let mut contents = String::new();
+    File::open(&path).unwrap().read_to_string(&mut contents).unwrap();
+    assert_eq!(contents, trace);
+    fs::remove_file(&path).unwrap();
+
+    timeline.clear();
+    assert_eq!(timeline.command_count(), 0);
+}
@@ -0,0 +1,64 @@
+//! Tests kernels declared using `ocl_kernel!`.
+
+use standard::{ProQue, Buffer};
+
+static SRC: &'static str = r#"
+    __kernel void scale(__global float* src, __global float* dst, float factor) {
+        dst[get_global_id(0)] = src[get_global_id(0)] * factor;
+    }
+
+    __kernel void fill(__global uint* buffer, uint value) {
+        buffer[get_global_id(0)] = value;
+    }
+"#;
+
+ocl_kernel!(fn scale(src: &Buffer<f32>, dst: &mut Buffer<f32>, factor: f32 as set_factor));
+
+ocl_kernel!(
+    /// Fills a buffer.
+    struct Fill: fn fill(buffer: &mut Buffer<u32>, value: u32,)
+);
+
+#[test]
+fn typed_kernel() {
+    let pro_que = ProQue::builder()
+        .src(SRC)
+        .dims([1024])
+        .build().unwrap();
+
+    let src = pro_que.create_buffer::<f32>().unwrap();
+    let mut dst = pro_que.create_buffer::<f32>().unwrap();
+    let mut filled = pro_que.create_buffer::<u32>().unwrap();
+
+    let mut fill = Fill::new(pro_que.program(), pro_que.queue().clone()).unwrap()
+        .gws(pro_que.dims());
+    fill.enq(&mut filled, 9).unwrap();
+
+    let mut vec_u32 = vec![0u32; filled.len()];
+    filled.read(&mut vec_u32).enq().unwrap();
+    assert!(vec_u32.iter().all(|&val| val == 9));
+
+    // Arguments are registered by name:
+    assert_eq!(fill.kernel().named_arg_idx("value"), Some(1));
+    fill.kernel_mut().set_arg_scl_named("value", 4u32).unwrap();
+    fill.kernel().enq().unwrap();
+    filled.read(&mut vec_u32).enq().unwrap();
+    assert!(vec_u32.iter().all(|&val| val == 4));
+
+    let vec_f32 = vec![2.0f32; src.len()];
+    src.write(&vec_f32).enq().unwrap();
+
+    let mut scale_kernel = scale::new(pro_que.program(), pro_que.queue().clone()).unwrap()
+        .gws(pro_que.dims());
+    scale_kernel.enq(&src, &mut dst, 1.5).unwrap();
+
+    let mut result = vec![0.0f32; dst.len()];
+    dst.read(&mut result).enq().unwrap();
+    assert!(result.iter().all(|&val| val == 3.0));
+
+    // Typed setters change a single argument:
+    scale_kernel.set_factor(0.5).unwrap();
+    scale_kernel.kernel().enq().unwrap();
+    dst.read(&mut result).enq().unwrap();
+    assert!(result.iter().all(|&val| val == 1.0));
+}
@@ -0,0 +1,81 @@
+//! Tests user events holding back enqueued commands.
+
+use std::thread;
+use std::time::Duration;
+use core::{EventInfo, EventInfoResult, CommandExecutionStatus};
+use standard::{ProQue, Event, UserEvent};
+
+static SRC: &'static str = r#"
+    __kernel void add(__global float* buffer, float addend) {
+        buffer[get_global_id(0)] += addend;
+    }
+"#;
+
+fn is_complete(event: &Event) -> bool {
+    match event.info(EventInfo::CommandExecutionStatus) {
+        EventInfoResult::CommandExecutionStatus(CommandExecutionStatus::Complete) => true,
+        _ => false,
+    }
+}
+
+#[test]
+fn user_event() {
+    let pro_que = ProQue::builder()
+        .src(SRC)
+        .dims([1024])
+        .build().unwrap();
+
+    let buffer = pro_que.create_buffer::<f32>().unwrap();
+
+    let kernel = pro_que.create_kernel("add").unwrap()
+        .arg_buf(&buffer)
+        .arg_scl(1.0f32);
+
+    // The kernel waits until the user event is set (from another thread):
+    let user_event = UserEvent::new(pro_que.context()).unwrap();
+    let mut kernel_event = Event::empty();
+    kernel.cmd().ewait(&user_event).enew(&mut kernel_event).enq().unwrap();
+
+    thread::sleep(Duration::from_millis(50));
+    assert!(!kernel_event.is_complete().unwrap());
+    assert!(!user_event.is_set());
+
+    let handle = thread::spawn(move || {
+        user_event.set_complete().unwrap();
+        // The status can only be set once:
+        assert!(user_event.set_complete().is_err());
+        assert!(user_event.set_error(-1).is_err());
+    });
+    handle.join().unwrap();
+
+    kernel_event.wait().unwrap();
+    let mut vec = vec![0.0f32; buffer.len()];
+    buffer.read(&mut vec).enq().unwrap();
+    assert!(vec.iter().all(|&val| val == 1.0));
+
+    // Error codes must be negative:
+    let user_event = UserEvent::new(pro_que.context()).unwrap();
+    assert!(user_event.set_error(0).is_err());
+    user_event.set_error(-42).unwrap();
+    assert!(user_event.is_set());
+    assert!(!is_complete(&user_event));
+
+    // Dropped without being set, with complete-on-drop enabled:
+    let mut user_event = UserEvent::new(pro_que.context()).unwrap();
+    user_event.set_complete_on_drop(true);
+    let event_clone = user_event.as_event().clone();
+    let mut kernel_event = Event::empty();
+    kernel.cmd().ewait(&user_event).enew(&mut kernel_event).enq().unwrap();
+    drop(user_event);
+
+    kernel_event.wait().unwrap();
+    assert!(is_complete(&event_clone));
+    buffer.read(&mut vec).enq().unwrap();
+    assert!(vec.iter().all(|&val| val == 2.0));
+
+    // Dropped without being set (the default fails it):
+    let user_event = UserEvent::new(pro_que.context()).unwrap();
+    let event_clone = user_event.as_event().clone();
+    drop(user_event);
+    assert!(!is_complete(&event_clone));
+}